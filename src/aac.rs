@@ -0,0 +1,101 @@
+//! Raw ADTS-framed AAC audio: duration and sample rate derived directly
+//! from the frame headers (ADTS streams carry no container-level
+//! metadata, unlike ID3-tagged MP3 or ISOBMFF `.m4a`).
+
+use crate::video::{TrackInfo, TrackInfoTag};
+use crate::{Error, Result};
+
+const ADTS_SAMPLE_RATES: [u32; 13] = [
+    96000, 88200, 64000, 48000, 44100, 32000, 24000, 22050, 16000, 12000, 11025, 8000, 7350,
+];
+
+/// Returns `true` if `data` starts with an ADTS sync word (12 set bits),
+/// i.e. this looks like a raw AAC file `MediaSource::has_track()` should
+/// recognize.
+pub(crate) fn has_adts_header(data: &[u8]) -> bool {
+    data.len() >= 7 && data[0] == 0xff && (data[1] & 0xf0) == 0xf0
+}
+
+/// Walks the ADTS frame sequence in `data`, summing per-frame sample
+/// counts to derive total duration, and returns a [`TrackInfo`] with
+/// only `DurationMs` and `SampleRate` set (this is audio-only, so
+/// `ImageWidth`/`ImageHeight` are absent).
+pub(crate) fn parse_adts(data: &[u8]) -> Result<TrackInfo> {
+    if !has_adts_header(data) {
+        return Err(Error::ParseFailed("not an ADTS AAC stream".into()));
+    }
+
+    let mut info = TrackInfo::default();
+    let mut offset = 0;
+    let mut sample_rate = 0u32;
+    let mut total_samples: u64 = 0;
+
+    while offset + 7 <= data.len() {
+        if data[offset] != 0xff || (data[offset + 1] & 0xf0) != 0xf0 {
+            break;
+        }
+
+        let has_crc = data[offset + 1] & 0x01 == 0;
+        let sr_index = ((data[offset + 2] >> 2) & 0x0f) as usize;
+        let frame_len = (((data[offset + 3] & 0x03) as usize) << 11)
+            | ((data[offset + 4] as usize) << 3)
+            | ((data[offset + 5] as usize) >> 5);
+
+        if frame_len < if has_crc { 9 } else { 7 } || offset + frame_len > data.len() {
+            break;
+        }
+
+        if sample_rate == 0 {
+            sample_rate = *ADTS_SAMPLE_RATES.get(sr_index).unwrap_or(&44100);
+        }
+        total_samples += 1024; // One AAC frame is always 1024 samples.
+
+        offset += frame_len;
+    }
+
+    if sample_rate > 0 {
+        let duration_ms = total_samples * 1000 / sample_rate as u64;
+        info.put(TrackInfoTag::DurationMs, duration_ms.to_string());
+        info.put(TrackInfoTag::SampleRate, sample_rate.to_string());
+    }
+
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adts_frame(sr_index: u8, payload_len: usize) -> Vec<u8> {
+        let frame_len = 7 + payload_len;
+        let mut frame = vec![
+            0xff,
+            0xf1, // MPEG-4, no CRC
+            (sr_index << 2) & 0xfc,
+            ((frame_len >> 11) & 0x03) as u8,
+            ((frame_len >> 3) & 0xff) as u8,
+            (((frame_len & 0x07) << 5) | 0x1f) as u8,
+            0xfc,
+        ];
+        frame.extend(std::iter::repeat(0u8).take(payload_len));
+        frame
+    }
+
+    #[test]
+    fn computes_duration_from_frame_count() {
+        let mut data = Vec::new();
+        for _ in 0..10 {
+            data.extend(adts_frame(4, 50)); // 44100Hz
+        }
+        assert!(has_adts_header(&data));
+        let info = parse_adts(&data).unwrap();
+        assert_eq!(
+            info.get(TrackInfoTag::DurationMs),
+            Some(&(10 * 1024 * 1000 / 44100).to_string().into())
+        );
+        assert_eq!(
+            info.get(TrackInfoTag::SampleRate),
+            Some(&44100.to_string().into())
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! Minimal ISOBMFF ("box"/"atom") scanning, shared by the `mov` (ISOBMFF
+//! video/audio), `heif` and `cr3` parsers.
+
+use crate::{Error, Result};
+
+/// A single parsed box: its 4-character type and its body (the bytes
+/// after the size+type header, or after the extended 64-bit size for a
+/// box whose declared size is `1`).
+pub(crate) struct BoxHolder<'a> {
+    pub(crate) box_type: [u8; 4],
+    pub(crate) data: &'a [u8],
+}
+
+/// Depth-first searches `data` for the first box of type `box_type`,
+/// recursing into each box's body when it isn't itself a match. Returns
+/// `(rest, Some(box))` with `rest` being the unconsumed tail of `data`
+/// after the top-level box that holds the match (the match itself, when
+/// it is top-level), or `(rest, None)` if no such box was found, with
+/// `rest` being any trailing bytes too short to form a complete box.
+pub(crate) fn find_box<'a>(
+    data: &'a [u8],
+    box_type: &str,
+) -> Result<(&'a [u8], Option<BoxHolder<'a>>)> {
+    let wanted = box_type.as_bytes();
+    if wanted.len() != 4 {
+        return Err(Error::ParseFailed(format!(
+            "box type must be 4 characters, got {box_type:?}"
+        )));
+    }
+
+    let mut rest = data;
+    while let Some((found, remaining)) = split_first_box(rest) {
+        if found.box_type == wanted {
+            return Ok((remaining, Some(found)));
+        }
+
+        if let (_, Some(found)) = find_box(found.data, box_type)? {
+            return Ok((remaining, Some(found)));
+        }
+
+        rest = remaining;
+    }
+
+    Ok((rest, None))
+}
+
+/// Splits the first box off the front of `data`, returning it and the
+/// unconsumed tail, or `None` if `data` doesn't start with a complete box.
+fn split_first_box(data: &[u8]) -> Option<(BoxHolder<'_>, &[u8])> {
+    if data.len() < 8 {
+        return None;
+    }
+    let size = u32::from_be_bytes([data[0], data[1], data[2], data[3]]) as usize;
+    let ty = [data[4], data[5], data[6], data[7]];
+
+    let (header_len, body_len) = if size == 1 {
+        if data.len() < 16 {
+            return None;
+        }
+        let large = u64::from_be_bytes(data[8..16].try_into().unwrap()) as usize;
+        (16, large.saturating_sub(16))
+    } else if size == 0 {
+        // Box extends to the end of the buffer.
+        (8, data.len() - 8)
+    } else {
+        (8, size.saturating_sub(8))
+    };
+
+    let total = header_len + body_len;
+    if total == 0 || total > data.len() {
+        return None;
+    }
+
+    Some((
+        BoxHolder { box_type: ty, data: &data[header_len..total] },
+        &data[total..],
+    ))
+}
+
+/// Splits `data` into its top-level boxes (no recursion into children),
+/// in order. Used where a box's body is itself a flat list of child
+/// boxes to enumerate (e.g. `iinf`'s `infe` entries), rather than
+/// something to search into for a single match like [`find_box`].
+pub(crate) fn top_level_boxes(data: &[u8]) -> Vec<BoxHolder<'_>> {
+    let mut out = Vec::new();
+    let mut rest = data;
+    while let Some((found, remaining)) = split_first_box(rest) {
+        out.push(found);
+        rest = remaining;
+    }
+    out
+}
+
+/// The UUID extension type (Adobe's registered XMP UUID) that marks a
+/// `uuid` box as carrying an XMP packet, per the ISO/IEC 23001-8 UUID
+/// box convention Adobe uses for XMP-in-MP4/HEIF.
+const XMP_UUID: [u8; 16] = [
+    0xBE, 0x7A, 0xCF, 0xCB, 0x97, 0xA9, 0x42, 0xE8, 0x9C, 0x71, 0x99, 0x94, 0x91, 0xE3, 0xAF, 0xAC,
+];
+
+/// Locates an embedded XMP packet in an ISOBMFF file (HEIF/HEIC, or an
+/// MP4/MOV carrying Adobe's XMP extension), checking first for a
+/// nonstandard `XMP_` box and then for a `uuid` box tagged with
+/// [`XMP_UUID`].
+pub(crate) fn find_xmp_packet<'a>(data: &'a [u8]) -> Result<Option<&'a [u8]>> {
+    if let (_, Some(found)) = find_box(data, "XMP_")? {
+        return Ok(Some(found.data));
+    }
+
+    let mut rest = data;
+    while let (remaining, Some(found)) = find_box(rest, "uuid")? {
+        if found.data.len() >= 16 && found.data[0..16] == XMP_UUID {
+            return Ok(Some(&found.data[16..]));
+        }
+        rest = remaining;
+        if remaining.is_empty() {
+            break;
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_box(ty: &[u8; 4], body: &[u8]) -> Vec<u8> {
+        let size = (8 + body.len()) as u32;
+        let mut out = size.to_be_bytes().to_vec();
+        out.extend_from_slice(ty);
+        out.extend_from_slice(body);
+        out
+    }
+
+    #[test]
+    fn finds_nested_box() {
+        let inner = make_box(b"CMT1", b"hello");
+        let outer = make_box(b"moov", &inner);
+        let (_, found) = find_box(&outer, "CMT1").unwrap();
+        assert_eq!(found.unwrap().data, b"hello");
+    }
+}
@@ -0,0 +1,106 @@
+//! Canon CR3 raw photo support.
+//!
+//! CR3 is ISOBMFF-based (like `.mov`/`.mp4`): the Exif payload lives in
+//! up to four `CMT1`..`CMT4` boxes inside `moov`, which between them hold
+//! the TIFF IFD0/Exif/GPS/Makernote sub-IFDs that a JPEG would pack into
+//! a single `APP1` segment. [`cr3_extract_exif`] concatenates them back
+//! into one TIFF-formatted blob so the rest of the pipeline can treat it
+//! like any other Exif payload.
+
+use std::io::{Cursor, Read, Seek};
+
+use crate::bbox::find_box;
+use crate::exif::check_exif_header;
+use crate::loader::BufLoader;
+use crate::mov::extract_moov_body_from_buf;
+use crate::skip::Seekable;
+use crate::{Error, Exif, Result};
+
+/// The `ftyp` brand that identifies a CR3 file.
+pub(crate) const CR3_BRAND: &[u8] = b"crx ";
+
+/// Returns `true` if `compatible_brands` (the space-padded list of
+/// 4-byte brands following `major_brand` in an ISOBMFF `ftyp` box)
+/// contains the CR3 brand.
+pub(crate) fn is_cr3_brand(compatible_brands: &[u8]) -> bool {
+    compatible_brands
+        .chunks_exact(4)
+        .any(|brand| brand == CR3_BRAND)
+}
+
+/// Extracts and concatenates the `CMT1`..`CMT4` box payloads from a CR3
+/// file's `moov` body, returning the combined TIFF-formatted Exif bytes
+/// (with any `Exif\0\0`/offset header already stripped), or `None` if no
+/// CMT boxes were found.
+#[tracing::instrument(skip_all)]
+pub(crate) fn cr3_extract_exif<R: Read + Seek>(reader: R) -> Result<Option<Vec<u8>>> {
+    let mut loader = BufLoader::<Seekable, _>::new(reader)?;
+    let moov_body_range = loader
+        .load_and_parse(extract_moov_body_from_buf)
+        .map_err(|e| Error::ParseFailed(format!("Failed to extract moov body: {e}")))?;
+
+    let file_bytes = loader.into_vec();
+    let moov_body = &file_bytes[moov_body_range];
+
+    let mut exif_data_segments = Vec::new();
+    for box_type in ["CMT1", "CMT2", "CMT3", "CMT4"] {
+        match find_box(moov_body, box_type) {
+            Ok((_, Some(box_holder))) => exif_data_segments.push(box_holder.data),
+            Ok((_, None)) => tracing::debug!("Box {} not found in moov body", box_type),
+            Err(e) => tracing::warn!("Error finding box {}: {:?}", box_type, e),
+        }
+    }
+
+    if exif_data_segments.is_empty() {
+        tracing::debug!("No CMT boxes with EXIF data found");
+        return Ok(None);
+    }
+
+    let data: Vec<u8> = exif_data_segments
+        .into_iter()
+        .flat_map(|d| d.to_vec())
+        .collect();
+
+    if data.len() >= 6 && check_exif_header(&data)? {
+        Ok(Some(data[6..].to_vec()))
+    } else if data.len() >= 10 && check_exif_header(&data[4..])? {
+        Ok(Some(data[10..].to_vec()))
+    } else if data.len() >= 8
+        && ((&data[0..2] == b"II" && data[2..4] == [0x2A, 0x00])
+            || (&data[0..2] == b"MM" && data[2..4] == [0x00, 0x2A]))
+    {
+        Ok(Some(data))
+    } else {
+        tracing::warn!("Could not find a valid EXIF/TIFF header in concatenated CMT data");
+        Ok(None)
+    }
+}
+
+/// Parses a CR3 file's Exif metadata directly.
+///
+/// This is the free-function equivalent of the unified workflow; prefer
+/// [`crate::MediaParser`] with [`crate::MediaSource`] for new code, which
+/// handles CR3 the same way as any other supported format.
+#[deprecated(note = "Please use `MediaParser` instead")]
+pub fn parse_cr3_exif<R: Read + Seek>(reader: R) -> Result<Option<Exif>> {
+    let Some(tiff) = cr3_extract_exif(reader)? else {
+        return Ok(None);
+    };
+
+    let mut prefixed = b"Exif\0\0".to_vec();
+    prefixed.extend_from_slice(&tiff);
+
+    #[allow(deprecated)]
+    crate::exif::parse_exif(Cursor::new(prefixed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_crx_brand() {
+        assert!(is_cr3_brand(b"crx heic"));
+        assert!(!is_cr3_brand(b"heic mif1"));
+    }
+}
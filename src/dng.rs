@@ -0,0 +1,65 @@
+//! DNG (Digital Negative) raw photo support.
+//!
+//! DNG is itself a TIFF file, so it's routed through the same TIFF IFD
+//! parser used for `.tiff`/`.tif`, with one difference: a DNG's Exif IFD
+//! usually isn't IFD0 itself but one of IFD0's `SubIFDs` (the full-size
+//! raw image is commonly stored as a separate sub-IFD from the
+//! preview/thumbnail in IFD0). `crate::exif`'s IFD parser walks the
+//! offsets [`sub_ifd_offsets`] returns and, for each, checks
+//! [`marks_exif_sub_ifd`] to find the one actually carrying Exif-style
+//! tags.
+
+use crate::exif::ExifTag;
+
+/// `SubIFDs` (0x014A): offsets to this IFD's child IFDs, as found in
+/// IFD0 of a DNG (and of many multi-page/thumbnail-carrying TIFFs).
+pub(crate) const TAG_SUB_IFDS: u16 = 0x014A;
+
+/// DNG-specific tags that have no equivalent in the standard Exif tag
+/// space. Exposed as raw tag ids (rather than added to [`ExifTag`]
+/// itself) since they only apply to this one raw format.
+pub mod tags {
+    /// `DNGVersion` (0xC612): the four-byte DNG spec version this file
+    /// conforms to.
+    pub const DNG_VERSION: u16 = 0xC612;
+    /// `UniqueCameraModel` (0xC614): a stable camera model identifier
+    /// shared across firmware revisions, unlike `Model`.
+    pub const UNIQUE_CAMERA_MODEL: u16 = 0xC614;
+    /// `AsShotNeutral` (0xC628): the camera-as-shot white balance, as
+    /// neutral multipliers (rather than a coordinate in a color space).
+    pub const AS_SHOT_NEUTRAL: u16 = 0xC628;
+    /// `ColorMatrix1` (0xC621): the first of up to two XYZ-to-camera
+    /// color matrices, calibrated under a specific illuminant.
+    pub const COLOR_MATRIX1: u16 = 0xC621;
+    /// `ColorMatrix2` (0xC622): the second calibration illuminant's
+    /// XYZ-to-camera color matrix.
+    pub const COLOR_MATRIX2: u16 = 0xC622;
+}
+
+/// Given the already-read value of IFD0's `SubIFDs` entry, returns the
+/// sub-IFD offsets it lists, in file order. The caller parses each as a
+/// normal IFD and picks the one that carries `ExifOffset`/Exif tags as
+/// the file's Exif IFD (DNGs commonly have one sub-IFD for the raw image
+/// and another for a JPEG preview).
+pub(crate) fn sub_ifd_offsets(ifd0_sub_ifds_value: &[u32]) -> impl Iterator<Item = u32> + '_ {
+    ifd0_sub_ifds_value.iter().copied()
+}
+
+/// Returns `true` if `tag` is one of the standard Exif tags that, when
+/// present in a parsed sub-IFD, marks it as the Exif IFD rather than a
+/// raw-image or thumbnail IFD.
+pub(crate) fn marks_exif_sub_ifd(tag: ExifTag) -> bool {
+    matches!(tag, ExifTag::ExifOffset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sub_ifd_offsets_preserves_order() {
+        let offsets = [100u32, 5000u32];
+        let collected: Vec<u32> = sub_ifd_offsets(&offsets).collect();
+        assert_eq!(collected, vec![100, 5000]);
+    }
+}
@@ -0,0 +1,35 @@
+//! Crate-wide error type.
+
+use std::fmt;
+
+/// The error type returned by every fallible operation in this crate.
+#[derive(Debug)]
+pub enum Error {
+    /// The input could not be parsed, for the given reason.
+    ParseFailed(String),
+    /// An I/O error occurred while reading the underlying source.
+    Io(std::io::Error),
+    /// The file format was not recognized.
+    UnrecognizedFileFormat,
+    /// No Exif data was found in an otherwise-recognized file.
+    ExifNotFound,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::ParseFailed(msg) => write!(f, "parse failed: {msg}"),
+            Error::Io(e) => write!(f, "io error: {e}"),
+            Error::UnrecognizedFileFormat => write!(f, "unrecognized file format"),
+            Error::ExifNotFound => write!(f, "Exif not found"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
@@ -0,0 +1,957 @@
+//! Exif parsing: a minimal TIFF/IFD walker shared by every container this
+//! crate supports (JPEG `APP1`, HEIF `Exif` items, TIFF/DNG files, RAF,
+//! CR3's concatenated `CMT` boxes, ...), plus the two public APIs built on
+//! top of it: [`Exif`] (*get* style) and [`ExifIter`] (*iterator* style).
+
+use crate::gps_track::{GpsTrack, GpsTrackOptions};
+use crate::values::{EntryValue, URational};
+use crate::{Error, Result};
+
+/// A tag in the Exif/TIFF tag space. Unrecognized tag ids are preserved
+/// as [`ExifTag::Unknown`] rather than dropped, and maker-note tags are
+/// namespaced by vendor via [`ExifTag::MakerNoteTag`] so they can't
+/// collide with standard tags or with another vendor's tag of the same
+/// id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExifTag {
+    Make,
+    Model,
+    Software,
+    Orientation,
+    ImageWidth,
+    ImageHeight,
+    ExifOffset,
+    GpsInfoOffset,
+    MakerNote,
+    SubIFDs,
+    CreateDate,
+    DateTimeOriginal,
+    OffsetTime,
+    OffsetTimeOriginal,
+    SubSecTime,
+    SubSecTimeOriginal,
+    /// A vendor-specific maker-note tag, decoded by [`crate::makernote`].
+    MakerNoteTag(crate::MakerNoteVendor, u16),
+    /// Any tag this crate doesn't have a named variant for yet.
+    Unknown(u16),
+}
+
+impl ExifTag {
+    pub(crate) fn from_id(id: u16) -> Self {
+        match id {
+            0x010F => ExifTag::Make,
+            0x0110 => ExifTag::Model,
+            0x0131 => ExifTag::Software,
+            0x0112 => ExifTag::Orientation,
+            0x0100 => ExifTag::ImageWidth,
+            0x0101 => ExifTag::ImageHeight,
+            0x8769 => ExifTag::ExifOffset,
+            0x8825 => ExifTag::GpsInfoOffset,
+            0x927C => ExifTag::MakerNote,
+            0x014A => ExifTag::SubIFDs,
+            0x9004 => ExifTag::CreateDate,
+            0x9003 => ExifTag::DateTimeOriginal,
+            0x9010 => ExifTag::OffsetTime,
+            0x9011 => ExifTag::OffsetTimeOriginal,
+            0x9290 => ExifTag::SubSecTime,
+            0x9291 => ExifTag::SubSecTimeOriginal,
+            other => ExifTag::Unknown(other),
+        }
+    }
+
+    /// The inverse of [`ExifTag::from_id`], recovering the numeric tag id
+    /// a [`ParsedExifEntry`] was parsed from. Needed when re-namespacing
+    /// an already-parsed entry (e.g. wrapping a maker-note entry's tag in
+    /// [`ExifTag::MakerNoteTag`] once its vendor is known).
+    pub(crate) fn raw_id(&self) -> u16 {
+        match self {
+            ExifTag::Make => 0x010F,
+            ExifTag::Model => 0x0110,
+            ExifTag::Software => 0x0131,
+            ExifTag::Orientation => 0x0112,
+            ExifTag::ImageWidth => 0x0100,
+            ExifTag::ImageHeight => 0x0101,
+            ExifTag::ExifOffset => 0x8769,
+            ExifTag::GpsInfoOffset => 0x8825,
+            ExifTag::MakerNote => 0x927C,
+            ExifTag::SubIFDs => 0x014A,
+            ExifTag::CreateDate => 0x9004,
+            ExifTag::DateTimeOriginal => 0x9003,
+            ExifTag::OffsetTime => 0x9010,
+            ExifTag::OffsetTimeOriginal => 0x9011,
+            ExifTag::SubSecTime => 0x9290,
+            ExifTag::SubSecTimeOriginal => 0x9291,
+            ExifTag::MakerNoteTag(_, id) | ExifTag::Unknown(id) => *id,
+        }
+    }
+}
+
+/// One decoded Exif entry: a tag paired with its value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedExifEntry {
+    pub tag: ExifTag,
+    pub value: EntryValue,
+}
+
+/// Iterator-style Exif access: entries in file order, produced by
+/// [`crate::MediaParser::parse`]. Convert into an [`Exif`] with
+/// `.into()` for *get*-style access instead.
+#[derive(Debug, Clone, Default)]
+pub struct ExifIter {
+    pub(crate) entries: Vec<ParsedExifEntry>,
+    pub(crate) gps_info: Option<GPSInfo>,
+}
+
+impl Iterator for ExifIter {
+    type Item = ParsedExifEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.entries.is_empty() {
+            None
+        } else {
+            Some(self.entries.remove(0))
+        }
+    }
+}
+
+impl ExifIter {
+    fn get(&self, tag: ExifTag) -> Option<&EntryValue> {
+        self.entries
+            .iter()
+            .find(|e| e.tag == tag)
+            .map(|e| &e.value)
+    }
+
+    /// Parses the standard `GPSLatitude`/`GPSLongitude`/... tags into a
+    /// [`GPSInfo`], if the file carried an embedded GPS IFD
+    /// (`GpsInfoOffset`). Returns `Ok(None)` — not an error — for a file
+    /// that simply has no GPS tags; callers in that situation should use
+    /// [`Self::interpolate_gps_from_track`] instead.
+    pub fn parse_gps_info(&self) -> Result<Option<GPSInfo>> {
+        Ok(self.gps_info)
+    }
+
+    /// Derives the photo's capture instant from `CreateDate` (falling
+    /// back to `DateTimeOriginal`), honoring `OffsetTimeOriginal`/
+    /// `OffsetTime` and `SubSecTimeOriginal`/`SubSecTime`, then
+    /// interpolates a [`GPSInfo`] for that instant from `track`.
+    ///
+    /// This is for files that have no embedded GPS tags of their own;
+    /// see [`GpsTrack`] for the geotagging workflow this completes.
+    pub fn interpolate_gps_from_track(
+        &mut self,
+        track: &GpsTrack,
+        opts: &GpsTrackOptions,
+    ) -> Result<GPSInfo> {
+        let capture_ms = self.capture_time_millis()?;
+        track.locate(capture_ms, opts)
+    }
+
+    fn capture_time_millis(&self) -> Result<i64> {
+        let date_str = self
+            .get(ExifTag::CreateDate)
+            .or_else(|| self.get(ExifTag::DateTimeOriginal))
+            .and_then(EntryValue::as_str)
+            .ok_or_else(|| Error::ParseFailed("no CreateDate/DateTimeOriginal tag".into()))?;
+
+        let offset_str = self
+            .get(ExifTag::OffsetTimeOriginal)
+            .or_else(|| self.get(ExifTag::OffsetTime))
+            .and_then(EntryValue::as_str);
+
+        let subsec_str = self
+            .get(ExifTag::SubSecTimeOriginal)
+            .or_else(|| self.get(ExifTag::SubSecTime))
+            .and_then(EntryValue::as_str);
+
+        parse_exif_datetime_millis(date_str, offset_str, subsec_str)
+    }
+}
+
+impl From<ExifIter> for Exif {
+    fn from(iter: ExifIter) -> Self {
+        Exif {
+            entries: iter.entries,
+            gps_info: iter.gps_info,
+        }
+    }
+}
+
+/// Get-style Exif access: a snapshot of every decoded entry.
+#[derive(Debug, Clone, Default)]
+pub struct Exif {
+    pub entries: Vec<ParsedExifEntry>,
+    gps_info: Option<GPSInfo>,
+}
+
+impl Exif {
+    pub fn get(&self, tag: ExifTag) -> Option<&EntryValue> {
+        self.entries
+            .iter()
+            .find(|e| e.tag == tag)
+            .map(|e| &e.value)
+    }
+
+    pub fn get_text(&self, tag: ExifTag) -> Option<String> {
+        self.get(tag).and_then(EntryValue::as_str).map(str::to_string)
+    }
+
+    pub fn get_uint(&self, tag: ExifTag) -> Option<u64> {
+        self.get(tag).and_then(EntryValue::as_uint)
+    }
+
+    pub fn get_gps_info(&self) -> Result<Option<GPSInfo>> {
+        Ok(self.gps_info)
+    }
+
+    /// Derives the capture instant from `CreateDate`/`DateTimeOriginal`
+    /// and interpolates a [`GPSInfo`] for it from `track`. See
+    /// [`ExifIter::interpolate_gps_from_track`] for the same operation
+    /// on the iterator-style type.
+    pub fn interpolate_gps_from_track(
+        &self,
+        track: &GpsTrack,
+        opts: &GpsTrackOptions,
+    ) -> Result<GPSInfo> {
+        let date_str = self
+            .get(ExifTag::CreateDate)
+            .or_else(|| self.get(ExifTag::DateTimeOriginal))
+            .and_then(EntryValue::as_str)
+            .ok_or_else(|| Error::ParseFailed("no CreateDate/DateTimeOriginal tag".into()))?;
+
+        let offset_str = self
+            .get(ExifTag::OffsetTimeOriginal)
+            .or_else(|| self.get(ExifTag::OffsetTime))
+            .and_then(EntryValue::as_str);
+
+        let subsec_str = self
+            .get(ExifTag::SubSecTimeOriginal)
+            .or_else(|| self.get(ExifTag::SubSecTime))
+            .and_then(EntryValue::as_str);
+
+        let capture_ms = parse_exif_datetime_millis(date_str, offset_str, subsec_str)?;
+        track.locate(capture_ms, opts)
+    }
+}
+
+/// Parses an Exif-style `"YYYY:MM:DD HH:MM:SS"` timestamp (optionally
+/// combined with an `OffsetTime*` tag like `"+08:00"` and a
+/// `SubSecTime*` tag like `"616"`) into Unix epoch milliseconds.
+fn parse_exif_datetime_millis(
+    date_str: &str,
+    offset_str: Option<&str>,
+    subsec_str: Option<&str>,
+) -> Result<i64> {
+    use chrono::NaiveDateTime;
+
+    let naive = NaiveDateTime::parse_from_str(date_str, "%Y:%m:%d %H:%M:%S")
+        .map_err(|e| Error::ParseFailed(format!("invalid Exif datetime {date_str:?}: {e}")))?;
+
+    let offset_secs = match offset_str {
+        Some(s) => parse_utc_offset_secs(s)?,
+        None => 0,
+    };
+
+    let millis = naive.and_utc().timestamp_millis() - offset_secs as i64 * 1000;
+
+    let subsec_millis = subsec_str
+        .and_then(|s| s.parse::<i64>().ok())
+        .map(|v| {
+            // `SubSecTime` is a string of arbitrary-length fractional-second
+            // digits, e.g. "616" means .616s and "6" means .6s (600ms);
+            // normalize to milliseconds by digit count.
+            let digits = subsec_str.map(str::len).unwrap_or(0) as u32;
+            if digits >= 3 {
+                v / 10i64.pow(digits - 3)
+            } else {
+                v * 10i64.pow(3 - digits)
+            }
+        })
+        .unwrap_or(0);
+
+    Ok(millis + subsec_millis)
+}
+
+/// Parses a `"+HH:MM"`/`"-HH:MM"` `OffsetTime*` value into signed
+/// seconds east of UTC.
+fn parse_utc_offset_secs(s: &str) -> Result<i32> {
+    let s = s.trim();
+    let (sign, rest) = match s.as_bytes().first() {
+        Some(b'+') => (1, &s[1..]),
+        Some(b'-') => (-1, &s[1..]),
+        _ => (1, s),
+    };
+    let mut parts = rest.split(':');
+    let hours: i32 = parts
+        .next()
+        .and_then(|p| p.parse().ok())
+        .ok_or_else(|| Error::ParseFailed(format!("invalid UTC offset {s:?}")))?;
+    let minutes: i32 = parts.next().and_then(|p| p.parse().ok()).unwrap_or(0);
+    Ok(sign * (hours * 3600 + minutes * 60))
+}
+
+/// GPS position, derived either from embedded GPS tags or from
+/// [`ExifIter::interpolate_gps_from_track`]/[`Exif::interpolate_gps_from_track`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GPSInfo {
+    pub latitude_ref: char,
+    pub latitude: LatLng,
+    pub longitude_ref: char,
+    pub longitude: LatLng,
+    pub altitude_ref: u8,
+    pub altitude: URational,
+    pub speed_ref: Option<char>,
+    pub speed: Option<URational>,
+}
+
+impl GPSInfo {
+    /// Formats this position as an ISO 6709 string, e.g.
+    /// `"+43.29013+084.22713+1595.950CRSWGS_84/"`.
+    pub fn format_iso6709(&self) -> String {
+        let lat = self.to_decimal(self.latitude, self.latitude_ref == 'S');
+        let lon = self.to_decimal(self.longitude, self.longitude_ref == 'W');
+        let alt_sign = if self.altitude_ref == 1 { '-' } else { '+' };
+        format!(
+            "{lat:+09.5}{lon:+010.5}{alt_sign}{:.3}CRSWGS_84/",
+            self.altitude.0 as f64 / self.altitude.1.max(1) as f64
+        )
+    }
+
+    fn to_decimal(&self, latlng: LatLng, negative: bool) -> f64 {
+        let [(d, dd), (m, md), (s, sd)] = latlng.0;
+        let decimal =
+            d as f64 / dd.max(1) as f64 + m as f64 / md.max(1) as f64 / 60.0
+                + s as f64 / sd.max(1) as f64 / 3600.0;
+        if negative {
+            -decimal
+        } else {
+            decimal
+        }
+    }
+}
+
+/// A latitude or longitude in the Exif/TIFF rational triple form:
+/// `[(deg,denom),(min,denom),(sec,denom)]`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatLng(pub [(u32, u32); 3]);
+
+impl From<[(u32, u32); 3]> for LatLng {
+    fn from(v: [(u32, u32); 3]) -> Self {
+        LatLng(v)
+    }
+}
+
+/// Returns `true` if `data` starts with the `Exif\0\0` header that marks
+/// a JPEG `APP1` segment (or a CR3 `CMT` box) as carrying Exif rather
+/// than XMP or some other payload.
+pub(crate) fn check_exif_header(data: &[u8]) -> Result<bool> {
+    Ok(data.len() >= 6 && &data[0..6] == b"Exif\0\0")
+}
+
+/// Parses a raw TIFF-formatted buffer (IFD0 onward, byte order taken
+/// from the first two bytes) into a flat list of entries, following
+/// `ExifOffset` into the Exif sub-IFD and merging its entries in, and a
+/// [`GPSInfo`] parsed out of the embedded GPS IFD (`GpsInfoOffset`), if
+/// the file has one.
+pub(crate) fn parse_tiff_ifd(tiff: &[u8]) -> Result<(Vec<ParsedExifEntry>, Option<GPSInfo>)> {
+    let is_le = match tiff.get(0..2) {
+        Some(b"II") => true,
+        Some(b"MM") => false,
+        _ => return Err(Error::ParseFailed("not a TIFF file (missing II/MM marker)".into())),
+    };
+    let ifd0_offset = read_u32(tiff, 4, is_le)? as usize;
+
+    let mut entries = Vec::new();
+    let mut gps_info = None;
+    parse_ifd_into(tiff, ifd0_offset, is_le, &mut entries, &mut gps_info, None)?;
+    Ok((entries, gps_info))
+}
+
+/// Parses one IFD at `offset` into `out`, recursing into `ExifOffset`'s
+/// target IFD (the standard Exif sub-IFD) when present, parsing
+/// `GpsInfoOffset`'s target IFD into `gps_info` when present, and
+/// dispatching `MakerNote` to [`crate::makernote::decode`] with the
+/// `Make` found in this IFD or, failing that, `parent_make` (`Make`
+/// lives in IFD0 while `MakerNote` lives in the Exif sub-IFD, so each
+/// recursion passes the `Make` it knows down to its child IFDs).
+fn parse_ifd_into(
+    tiff: &[u8],
+    offset: usize,
+    is_le: bool,
+    out: &mut Vec<ParsedExifEntry>,
+    gps_info: &mut Option<GPSInfo>,
+    parent_make: Option<&str>,
+) -> Result<()> {
+    let count = read_u16(tiff, offset, is_le)? as usize;
+    let mut exif_sub_ifd = None;
+    let mut gps_sub_ifd = None;
+    let mut make: Option<String> = parent_make.map(str::to_string);
+    let mut maker_note_offset = None;
+    let mut sub_ifd_offsets: Option<Vec<u32>> = None;
+
+    for i in 0..count {
+        let entry_offset = offset + 2 + i * 12;
+        let (tag_id, value_type, value_slice) = read_entry_value(tiff, entry_offset, is_le)?;
+        let tag = ExifTag::from_id(tag_id);
+
+        if tag == ExifTag::ExifOffset {
+            exif_sub_ifd = Some(read_u32(tiff, entry_offset + 8, is_le)? as usize);
+            continue;
+        }
+
+        if tag == ExifTag::GpsInfoOffset {
+            gps_sub_ifd = Some(read_u32(tiff, entry_offset + 8, is_le)? as usize);
+            continue;
+        }
+
+        if tag == ExifTag::SubIFDs {
+            let value_count = read_u32(tiff, entry_offset + 4, is_le)? as usize;
+            let base = entry_value_offset(tiff, entry_offset, value_type, is_le)?;
+            // The declared count is untrusted input: bound it by what could
+            // actually fit (4 bytes/offset) in the remaining buffer before
+            // trusting it as a `Vec` capacity, so a crafted huge count can't
+            // force a multi-gigabyte allocation.
+            let max_count = tiff.len().saturating_sub(base) / 4;
+            if value_count > max_count {
+                return Err(Error::ParseFailed(
+                    "SubIFDs entry count exceeds buffer bounds".into(),
+                ));
+            }
+            let mut offsets = Vec::with_capacity(value_count);
+            for j in 0..value_count {
+                offsets.push(read_u32(tiff, base + j * 4, is_le)?);
+            }
+            sub_ifd_offsets = Some(offsets);
+            continue;
+        }
+
+        let value = decode_value(value_type, value_slice, is_le);
+
+        if tag == ExifTag::Make {
+            if let EntryValue::Text(ref s) = value {
+                make = Some(s.clone());
+            }
+        }
+
+        if tag == ExifTag::MakerNote {
+            maker_note_offset = Some(entry_value_offset(tiff, entry_offset, value_type, is_le)?);
+        }
+
+        out.push(ParsedExifEntry { tag, value });
+    }
+
+    if let Some(sub_ifd_offset) = exif_sub_ifd {
+        parse_ifd_into(tiff, sub_ifd_offset, is_le, out, gps_info, make.as_deref())?;
+    }
+
+    if let Some(offset) = gps_sub_ifd {
+        if let Some(info) = parse_gps_ifd(tiff, offset, is_le)? {
+            *gps_info = Some(info);
+        }
+    }
+
+    // DNG (and other multi-page TIFFs) keep the Exif-bearing IFD under
+    // `SubIFDs` rather than as IFD0 itself: scan each candidate's flat
+    // entry list for `ExifOffset` and fully parse (following *its*
+    // `ExifOffset`/`MakerNote`) whichever one has it.
+    if let Some(offsets) = sub_ifd_offsets {
+        for candidate in crate::dng::sub_ifd_offsets(&offsets) {
+            let candidate = candidate as usize;
+            let flat = parse_ifd_entries(tiff, candidate, is_le)?;
+            if flat.iter().any(|e| crate::dng::marks_exif_sub_ifd(e.tag)) {
+                parse_ifd_into(tiff, candidate, is_le, out, gps_info, make.as_deref())?;
+                break;
+            }
+        }
+    }
+
+    if let (Some(mn_offset), Some(make)) = (maker_note_offset, &make) {
+        if let Some((vendor, entries)) = crate::makernote::decode(make, tiff, mn_offset, is_le)? {
+            out.extend(entries.into_iter().map(|e| ParsedExifEntry {
+                tag: ExifTag::MakerNoteTag(vendor, e.tag.raw_id()),
+                value: e.value,
+            }));
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolves an IFD entry's value location: the 4-byte value field itself
+/// when the value fits inline, or the offset it points to otherwise.
+fn entry_value_offset(
+    tiff: &[u8],
+    entry_offset: usize,
+    value_type: u16,
+    is_le: bool,
+) -> Result<usize> {
+    let value_count = read_u32(tiff, entry_offset + 4, is_le)? as usize;
+    let value_bytes_offset = entry_offset + 8;
+    if type_size(value_type) * value_count <= 4 {
+        Ok(value_bytes_offset)
+    } else {
+        Ok(read_u32(tiff, value_bytes_offset, is_le)? as usize)
+    }
+}
+
+/// Reads one 12-byte IFD entry at `entry_offset`, resolving its value
+/// (inline or offset-addressed) against `buf`.
+fn read_entry_value(buf: &[u8], entry_offset: usize, is_le: bool) -> Result<(u16, u16, &[u8])> {
+    let tag_id = read_u16(buf, entry_offset, is_le)?;
+    let value_type = read_u16(buf, entry_offset + 2, is_le)?;
+    let value_count = read_u32(buf, entry_offset + 4, is_le)? as usize;
+    let value_bytes_offset = entry_offset + 8;
+
+    let total_size = type_size(value_type) * value_count;
+    let value_slice = if total_size <= 4 {
+        buf.get(value_bytes_offset..value_bytes_offset + total_size)
+    } else {
+        let off = read_u32(buf, value_bytes_offset, is_le)? as usize;
+        buf.get(off..off + total_size)
+    }
+    .ok_or_else(|| Error::ParseFailed("IFD entry value is out of range".into()))?;
+
+    Ok((tag_id, value_type, value_slice))
+}
+
+/// Parses a single flat IFD at `offset` within `buf` (2-byte entry count,
+/// then 12-byte entries; any offset-valued entry is resolved against
+/// `buf` itself) without following `ExifOffset`/`MakerNote`/`SubIFDs`.
+/// Used by [`crate::makernote`] to walk a maker note's own IFD-shaped
+/// body, whose offset base varies by vendor (the enclosing TIFF for
+/// Canon, a nested embedded TIFF for Nikon, the maker note's own start
+/// for Fujifilm).
+pub(crate) fn parse_ifd_entries(buf: &[u8], offset: usize, is_le: bool) -> Result<Vec<ParsedExifEntry>> {
+    let count = read_u16(buf, offset, is_le)? as usize;
+    let mut out = Vec::with_capacity(count);
+    for i in 0..count {
+        let entry_offset = offset + 2 + i * 12;
+        let (tag_id, value_type, value_slice) = read_entry_value(buf, entry_offset, is_le)?;
+        out.push(ParsedExifEntry {
+            tag: ExifTag::from_id(tag_id),
+            value: decode_value(value_type, value_slice, is_le),
+        });
+    }
+    Ok(out)
+}
+
+/// Parses a GPS sub-IFD (the IFD `GpsInfoOffset` points to) into a
+/// [`GPSInfo`]. GPS tag ids occupy their own small, fixed namespace that
+/// overlaps numerically with IFD0's (e.g. `GPSLatitudeRef` is `0x0001`,
+/// same id as a totally unrelated tag elsewhere), so this walks the
+/// entries by raw id directly rather than through [`ExifTag::from_id`].
+/// It also bypasses the general-purpose [`decode_value`], which only
+/// ever decodes a single RATIONAL and would silently drop two of
+/// `GPSLatitude`/`GPSLongitude`'s three.
+fn parse_gps_ifd(tiff: &[u8], offset: usize, is_le: bool) -> Result<Option<GPSInfo>> {
+    const GPS_LATITUDE_REF: u16 = 0x0001;
+    const GPS_LATITUDE: u16 = 0x0002;
+    const GPS_LONGITUDE_REF: u16 = 0x0003;
+    const GPS_LONGITUDE: u16 = 0x0004;
+    const GPS_ALTITUDE_REF: u16 = 0x0005;
+    const GPS_ALTITUDE: u16 = 0x0006;
+    const GPS_SPEED_REF: u16 = 0x000C;
+    const GPS_SPEED: u16 = 0x000D;
+
+    let count = read_u16(tiff, offset, is_le)? as usize;
+
+    let mut latitude_ref = None;
+    let mut latitude = None;
+    let mut longitude_ref = None;
+    let mut longitude = None;
+    let mut altitude_ref = 0u8;
+    let mut altitude = URational(0, 1);
+    let mut speed_ref = None;
+    let mut speed = None;
+
+    for i in 0..count {
+        let entry_offset = offset + 2 + i * 12;
+        let (tag_id, _value_type, value_slice) = read_entry_value(tiff, entry_offset, is_le)?;
+
+        match tag_id {
+            GPS_LATITUDE_REF => latitude_ref = ascii_char(value_slice),
+            GPS_LATITUDE => latitude = read_latlng(value_slice, is_le),
+            GPS_LONGITUDE_REF => longitude_ref = ascii_char(value_slice),
+            GPS_LONGITUDE => longitude = read_latlng(value_slice, is_le),
+            GPS_ALTITUDE_REF => altitude_ref = value_slice.first().copied().unwrap_or(0),
+            GPS_ALTITUDE => altitude = read_rational(value_slice, is_le).unwrap_or(altitude),
+            GPS_SPEED_REF => speed_ref = ascii_char(value_slice),
+            GPS_SPEED => speed = read_rational(value_slice, is_le),
+            _ => {}
+        }
+    }
+
+    let (Some(latitude_ref), Some(latitude), Some(longitude_ref), Some(longitude)) =
+        (latitude_ref, latitude, longitude_ref, longitude)
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(GPSInfo {
+        latitude_ref,
+        latitude,
+        longitude_ref,
+        longitude,
+        altitude_ref,
+        altitude,
+        speed_ref,
+        speed,
+    }))
+}
+
+/// Reads a GPS `*Ref` tag's value (e.g. `"N\0"`) as its first character.
+fn ascii_char(bytes: &[u8]) -> Option<char> {
+    bytes.first().map(|&b| b as char)
+}
+
+/// Reads a single RATIONAL value (e.g. `GPSAltitude`/`GPSSpeed`).
+fn read_rational(bytes: &[u8], is_le: bool) -> Option<URational> {
+    if bytes.len() < 8 {
+        return None;
+    }
+    Some(URational(
+        read_u32_from(&bytes[0..4], is_le),
+        read_u32_from(&bytes[4..8], is_le),
+    ))
+}
+
+/// Reads a `GPSLatitude`/`GPSLongitude` value: three back-to-back
+/// RATIONALs (degrees, minutes, seconds).
+fn read_latlng(bytes: &[u8], is_le: bool) -> Option<LatLng> {
+    if bytes.len() < 24 {
+        return None;
+    }
+    let mut parts = [(0u32, 0u32); 3];
+    for (i, part) in parts.iter_mut().enumerate() {
+        let chunk = &bytes[i * 8..i * 8 + 8];
+        *part = (
+            read_u32_from(&chunk[0..4], is_le),
+            read_u32_from(&chunk[4..8], is_le),
+        );
+    }
+    Some(LatLng(parts))
+}
+
+fn type_size(value_type: u16) -> usize {
+    match value_type {
+        1 | 2 | 6 | 7 => 1,       // BYTE, ASCII, SBYTE, UNDEFINED
+        3 | 8 => 2,               // SHORT, SSHORT
+        4 | 9 => 4,               // LONG, SLONG
+        5 | 10 => 8,              // RATIONAL, SRATIONAL
+        _ => 1,
+    }
+}
+
+fn decode_value(value_type: u16, bytes: &[u8], is_le: bool) -> EntryValue {
+    match value_type {
+        2 => EntryValue::Text(
+            String::from_utf8_lossy(bytes)
+                .trim_end_matches('\0')
+                .to_string(),
+        ),
+        3 if bytes.len() >= 2 => EntryValue::U32(read_u16_from(bytes, is_le) as u32),
+        4 if bytes.len() >= 4 => EntryValue::U32(read_u32_from(bytes, is_le)),
+        5 if bytes.len() >= 8 => EntryValue::URational(URational(
+            read_u32_from(&bytes[0..4], is_le),
+            read_u32_from(&bytes[4..8], is_le),
+        )),
+        _ => EntryValue::Undefined(bytes.to_vec()),
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize, is_le: bool) -> Result<u16> {
+    let b = data
+        .get(offset..offset + 2)
+        .ok_or_else(|| Error::ParseFailed("TIFF offset out of range".into()))?;
+    Ok(read_u16_from(b, is_le))
+}
+
+pub(crate) fn read_u32(data: &[u8], offset: usize, is_le: bool) -> Result<u32> {
+    let b = data
+        .get(offset..offset + 4)
+        .ok_or_else(|| Error::ParseFailed("TIFF offset out of range".into()))?;
+    Ok(read_u32_from(b, is_le))
+}
+
+fn read_u16_from(b: &[u8], is_le: bool) -> u16 {
+    if is_le {
+        u16::from_le_bytes([b[0], b[1]])
+    } else {
+        u16::from_be_bytes([b[0], b[1]])
+    }
+}
+
+fn read_u32_from(b: &[u8], is_le: bool) -> u32 {
+    if is_le {
+        u32::from_le_bytes([b[0], b[1], b[2], b[3]])
+    } else {
+        u32::from_be_bytes([b[0], b[1], b[2], b[3]])
+    }
+}
+
+#[deprecated(note = "Please use `MediaParser` instead")]
+pub fn parse_exif<R: std::io::Read + std::io::Seek>(mut reader: R) -> Result<Option<Exif>> {
+    use std::io::Read as _;
+
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+
+    let tiff = if check_exif_header(&data)? {
+        &data[6..]
+    } else {
+        &data[..]
+    };
+
+    if tiff.is_empty() {
+        return Ok(None);
+    }
+
+    let (entries, gps_info) = parse_tiff_ifd(tiff)?;
+    Ok(Some(ExifIter { entries, gps_info }.into()))
+}
+
+#[cfg(feature = "async")]
+#[deprecated(note = "Please use `AsyncMediaParser` instead")]
+pub async fn parse_exif_async<R>(_reader: R) -> Result<Option<Exif>> {
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpolates_gps_from_create_date() {
+        let gpx = r#"
+            <gpx><trk><trkseg>
+                <trkpt lat="22.0" lon="113.0"><time>2024-02-02T08:09:50Z</time></trkpt>
+                <trkpt lat="22.1" lon="113.1"><time>2024-02-02T08:10:10Z</time></trkpt>
+            </trkseg></trk></gpx>
+        "#;
+        let track = GpsTrack::from_gpx(gpx).unwrap();
+
+        let mut iter = ExifIter {
+            entries: vec![
+                ParsedExifEntry {
+                    tag: ExifTag::CreateDate,
+                    value: "2024:02:02 08:10:00".into(),
+                },
+                ParsedExifEntry {
+                    tag: ExifTag::OffsetTimeOriginal,
+                    value: "+00:00".into(),
+                },
+            ],
+            gps_info: None,
+        };
+
+        let gps = iter
+            .interpolate_gps_from_track(&track, &GpsTrackOptions::default())
+            .unwrap();
+        assert_eq!(gps.latitude_ref, 'N');
+    }
+
+    #[test]
+    fn dispatches_maker_note_decoding_from_ifd_parser() {
+        let make_value = b"FUJIFILM\0";
+        let mut maker_note = Vec::new();
+        maker_note.extend_from_slice(b"FUJIFILM");
+        maker_note.extend_from_slice(&12u32.to_le_bytes()); // IFD offset within the note
+        maker_note.extend_from_slice(&1u16.to_le_bytes()); // entry count
+        maker_note.extend_from_slice(&0x1000u16.to_le_bytes()); // tag id
+        maker_note.extend_from_slice(&2u16.to_le_bytes()); // type: ASCII
+        maker_note.extend_from_slice(&4u32.to_le_bytes()); // count
+        maker_note.extend_from_slice(b"RAW\0"); // inline value
+
+        let mut tiff = Vec::new();
+        tiff.extend_from_slice(b"II");
+        tiff.extend_from_slice(&0x2Au16.to_le_bytes());
+        tiff.extend_from_slice(&8u32.to_le_bytes()); // ifd0 offset
+
+        let ifd0_start = tiff.len();
+        let make_offset = ifd0_start + 2 + 2 * 12 + 4;
+        let maker_note_offset = make_offset + make_value.len();
+
+        tiff.extend_from_slice(&2u16.to_le_bytes()); // entry count
+        tiff.extend_from_slice(&0x010Fu16.to_le_bytes()); // Make
+        tiff.extend_from_slice(&2u16.to_le_bytes());
+        tiff.extend_from_slice(&(make_value.len() as u32).to_le_bytes());
+        tiff.extend_from_slice(&(make_offset as u32).to_le_bytes());
+        tiff.extend_from_slice(&0x927Cu16.to_le_bytes()); // MakerNote
+        tiff.extend_from_slice(&7u16.to_le_bytes());
+        tiff.extend_from_slice(&(maker_note.len() as u32).to_le_bytes());
+        tiff.extend_from_slice(&(maker_note_offset as u32).to_le_bytes());
+        tiff.extend_from_slice(&0u32.to_le_bytes()); // next IFD offset
+        tiff.extend_from_slice(make_value);
+        tiff.extend_from_slice(&maker_note);
+
+        let (entries, _) = parse_tiff_ifd(&tiff).unwrap();
+        let decoded = entries.iter().find(|e| {
+            matches!(
+                e.tag,
+                ExifTag::MakerNoteTag(crate::MakerNoteVendor::Fujifilm, 0x1000)
+            )
+        });
+        assert!(
+            decoded.is_some(),
+            "expected the IFD parser to dispatch the MakerNote tag to a decoded Fujifilm entry"
+        );
+        assert_eq!(decoded.unwrap().value, EntryValue::Text("RAW".into()));
+    }
+
+    #[test]
+    fn follows_dng_sub_ifds_to_the_exif_bearing_one() {
+        let mut tiff = Vec::new();
+        tiff.extend_from_slice(b"II");
+        tiff.extend_from_slice(&0x2Au16.to_le_bytes());
+        tiff.extend_from_slice(&8u32.to_le_bytes()); // ifd0 offset
+
+        let ifd0_start = tiff.len();
+        let sub_ifds_array_offset = ifd0_start + 2 + 12 + 4;
+        tiff.extend_from_slice(&1u16.to_le_bytes()); // ifd0 entry count
+        tiff.extend_from_slice(&0x014Au16.to_le_bytes()); // SubIFDs
+        tiff.extend_from_slice(&4u16.to_le_bytes()); // type: LONG
+        tiff.extend_from_slice(&2u32.to_le_bytes()); // count: 2 sub-IFDs
+        tiff.extend_from_slice(&(sub_ifds_array_offset as u32).to_le_bytes());
+        tiff.extend_from_slice(&0u32.to_le_bytes()); // ifd0 next-IFD offset
+        assert_eq!(tiff.len(), sub_ifds_array_offset);
+
+        let raw_ifd_offset = sub_ifds_array_offset + 2 * 4;
+        let exif_ifd_offset = raw_ifd_offset + 2 + 12 + 4;
+        tiff.extend_from_slice(&(raw_ifd_offset as u32).to_le_bytes());
+        tiff.extend_from_slice(&(exif_ifd_offset as u32).to_le_bytes());
+        assert_eq!(tiff.len(), raw_ifd_offset);
+
+        // Raw-image sub-IFD: no ExifOffset, so it's skipped as a
+        // candidate even though it's listed first.
+        tiff.extend_from_slice(&1u16.to_le_bytes());
+        tiff.extend_from_slice(&0x0100u16.to_le_bytes()); // ImageWidth
+        tiff.extend_from_slice(&4u16.to_le_bytes());
+        tiff.extend_from_slice(&1u32.to_le_bytes());
+        tiff.extend_from_slice(&100u32.to_le_bytes());
+        tiff.extend_from_slice(&0u32.to_le_bytes());
+        assert_eq!(tiff.len(), exif_ifd_offset);
+
+        let real_exif_ifd_offset = exif_ifd_offset + 2 + 12 + 4;
+        tiff.extend_from_slice(&1u16.to_le_bytes());
+        tiff.extend_from_slice(&0x8769u16.to_le_bytes()); // ExifOffset
+        tiff.extend_from_slice(&4u16.to_le_bytes());
+        tiff.extend_from_slice(&1u32.to_le_bytes());
+        tiff.extend_from_slice(&(real_exif_ifd_offset as u32).to_le_bytes());
+        tiff.extend_from_slice(&0u32.to_le_bytes());
+        assert_eq!(tiff.len(), real_exif_ifd_offset);
+
+        let date_value = b"2024:01:01 00:00:00\0";
+        let date_value_offset = real_exif_ifd_offset + 2 + 12 + 4;
+        tiff.extend_from_slice(&1u16.to_le_bytes());
+        tiff.extend_from_slice(&0x9003u16.to_le_bytes()); // DateTimeOriginal
+        tiff.extend_from_slice(&2u16.to_le_bytes()); // ASCII
+        tiff.extend_from_slice(&(date_value.len() as u32).to_le_bytes());
+        tiff.extend_from_slice(&(date_value_offset as u32).to_le_bytes());
+        tiff.extend_from_slice(&0u32.to_le_bytes());
+        assert_eq!(tiff.len(), date_value_offset);
+        tiff.extend_from_slice(date_value);
+
+        let (entries, _) = parse_tiff_ifd(&tiff).unwrap();
+        let found = entries.iter().find(|e| e.tag == ExifTag::DateTimeOriginal);
+        assert!(
+            found.is_some(),
+            "expected the DNG parser to follow SubIFDs into the Exif-bearing sub-IFD"
+        );
+        assert_eq!(
+            found.unwrap().value,
+            EntryValue::Text("2024:01:01 00:00:00".to_string())
+        );
+
+        // The raw-image sub-IFD was correctly skipped: its tag never
+        // makes it into the merged entries.
+        assert!(entries.iter().all(|e| e.tag != ExifTag::ImageWidth));
+    }
+
+    #[test]
+    fn rejects_sub_ifds_count_that_exceeds_the_buffer_instead_of_allocating() {
+        let mut tiff = Vec::new();
+        tiff.extend_from_slice(b"II");
+        tiff.extend_from_slice(&0x2Au16.to_le_bytes());
+        tiff.extend_from_slice(&8u32.to_le_bytes()); // ifd0 offset
+
+        let ifd0_start = tiff.len();
+        let sub_ifds_array_offset = ifd0_start + 2 + 12 + 4;
+        tiff.extend_from_slice(&1u16.to_le_bytes()); // ifd0 entry count
+        tiff.extend_from_slice(&0x014Au16.to_le_bytes()); // SubIFDs
+        tiff.extend_from_slice(&4u16.to_le_bytes()); // type: LONG
+        tiff.extend_from_slice(&0xFFFF_FFFFu32.to_le_bytes()); // absurd count
+        tiff.extend_from_slice(&(sub_ifds_array_offset as u32).to_le_bytes());
+        tiff.extend_from_slice(&0u32.to_le_bytes()); // ifd0 next-IFD offset
+
+        let err = parse_tiff_ifd(&tiff).unwrap_err();
+        assert!(matches!(err, Error::ParseFailed(_)));
+    }
+
+    #[test]
+    fn parses_embedded_gps_ifd_via_gps_info_offset() {
+        let mut tiff = Vec::new();
+        tiff.extend_from_slice(b"II");
+        tiff.extend_from_slice(&0x2Au16.to_le_bytes());
+        tiff.extend_from_slice(&8u32.to_le_bytes()); // ifd0 offset
+
+        let ifd0_start = tiff.len();
+        let gps_ifd_offset = ifd0_start + 2 + 12 + 4;
+        tiff.extend_from_slice(&1u16.to_le_bytes()); // ifd0 entry count
+        tiff.extend_from_slice(&0x8825u16.to_le_bytes()); // GpsInfoOffset
+        tiff.extend_from_slice(&4u16.to_le_bytes()); // type: LONG
+        tiff.extend_from_slice(&1u32.to_le_bytes()); // count
+        tiff.extend_from_slice(&(gps_ifd_offset as u32).to_le_bytes());
+        tiff.extend_from_slice(&0u32.to_le_bytes()); // ifd0 next-IFD offset
+        assert_eq!(tiff.len(), gps_ifd_offset);
+
+        let entries_start = gps_ifd_offset + 2;
+        let lat_data_offset = entries_start + 4 * 12 + 4;
+        let lon_data_offset = lat_data_offset + 24;
+
+        tiff.extend_from_slice(&4u16.to_le_bytes()); // gps ifd entry count
+        tiff.extend_from_slice(&1u16.to_le_bytes()); // GPSLatitudeRef
+        tiff.extend_from_slice(&2u16.to_le_bytes()); // ASCII
+        tiff.extend_from_slice(&2u32.to_le_bytes());
+        tiff.extend_from_slice(b"N\0\0\0");
+        tiff.extend_from_slice(&2u16.to_le_bytes()); // GPSLatitude
+        tiff.extend_from_slice(&5u16.to_le_bytes()); // RATIONAL
+        tiff.extend_from_slice(&3u32.to_le_bytes());
+        tiff.extend_from_slice(&(lat_data_offset as u32).to_le_bytes());
+        tiff.extend_from_slice(&3u16.to_le_bytes()); // GPSLongitudeRef
+        tiff.extend_from_slice(&2u16.to_le_bytes()); // ASCII
+        tiff.extend_from_slice(&2u32.to_le_bytes());
+        tiff.extend_from_slice(b"E\0\0\0");
+        tiff.extend_from_slice(&4u16.to_le_bytes()); // GPSLongitude
+        tiff.extend_from_slice(&5u16.to_le_bytes()); // RATIONAL
+        tiff.extend_from_slice(&3u32.to_le_bytes());
+        tiff.extend_from_slice(&(lon_data_offset as u32).to_le_bytes());
+        tiff.extend_from_slice(&0u32.to_le_bytes()); // gps ifd next-IFD offset
+        assert_eq!(tiff.len(), lat_data_offset);
+
+        for (num, denom) in [(43u32, 1u32), (17, 1), (2446, 100)] {
+            tiff.extend_from_slice(&num.to_le_bytes());
+            tiff.extend_from_slice(&denom.to_le_bytes());
+        }
+        assert_eq!(tiff.len(), lon_data_offset);
+        for (num, denom) in [(84u32, 1u32), (13, 1), (5377, 100)] {
+            tiff.extend_from_slice(&num.to_le_bytes());
+            tiff.extend_from_slice(&denom.to_le_bytes());
+        }
+
+        let (entries, gps_info) = parse_tiff_ifd(&tiff).unwrap();
+        let gps_info = gps_info.expect("expected a GPSInfo parsed from the embedded GPS IFD");
+        assert_eq!(gps_info.latitude_ref, 'N');
+        assert_eq!(gps_info.longitude_ref, 'E');
+        assert_eq!(gps_info.latitude, [(43, 1), (17, 1), (2446, 100)].into());
+        assert_eq!(gps_info.longitude, [(84, 1), (13, 1), (5377, 100)].into());
+
+        let mut iter = ExifIter {
+            entries,
+            gps_info: Some(gps_info),
+        };
+        assert_eq!(iter.parse_gps_info().unwrap(), Some(gps_info));
+
+        let exif: Exif = iter.into();
+        assert_eq!(exif.get_gps_info().unwrap(), Some(gps_info));
+    }
+}
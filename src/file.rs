@@ -0,0 +1,168 @@
+//! File format sniffing: picks the right container-specific parser for
+//! a buffer of bytes based on its signature, independent of file
+//! extension.
+
+use crate::cr3::is_cr3_brand;
+use crate::{aac, id3, Error, Result};
+
+/// The recognized container format of a `MediaSource`, used to route it
+/// to the right Exif/XMP/track-info extractor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    Jpeg,
+    Heif,
+    Tiff,
+    Raf,
+    Cr3,
+    Dng,
+    Mov,
+    Mp4,
+    WebM,
+    Matroska,
+    Mp3,
+    Aac,
+}
+
+impl FileFormat {
+    /// Sniffs `data`'s format from its leading bytes.
+    pub(crate) fn detect(data: &[u8]) -> Result<Self> {
+        if data.starts_with(&[0xFF, 0xD8]) {
+            return Ok(FileFormat::Jpeg);
+        }
+
+        if data.starts_with(b"FUJIFILMCCD-RAW") {
+            return Ok(FileFormat::Raf);
+        }
+
+        if id3::has_id3_header(data) {
+            return Ok(FileFormat::Mp3);
+        }
+
+        if aac::has_adts_header(data) {
+            return Ok(FileFormat::Aac);
+        }
+
+        if data.len() >= 16 && &data[4..8] == b"ftyp" {
+            let ftyp_end = (u32::from_be_bytes([data[0], data[1], data[2], data[3]]) as usize)
+                .clamp(16, data.len());
+            let major_brand = &data[8..12];
+            let compatible = &data[16..ftyp_end];
+            if is_cr3_brand(compatible) || major_brand == b"crx " {
+                return Ok(FileFormat::Cr3);
+            }
+            if matches!(major_brand, b"heic" | b"heix" | b"mif1" | b"heim" | b"heis") {
+                return Ok(FileFormat::Heif);
+            }
+            if matches!(major_brand, b"qt  ") {
+                return Ok(FileFormat::Mov);
+            }
+            return Ok(FileFormat::Mp4);
+        }
+
+        if data.len() >= 4
+            && ((&data[0..2] == b"II" && data[2..4] == [0x2A, 0x00])
+                || (&data[0..2] == b"MM" && data[2..4] == [0x00, 0x2A]))
+        {
+            return Ok(if is_dng(data) {
+                FileFormat::Dng
+            } else {
+                FileFormat::Tiff
+            });
+        }
+
+        if data.starts_with(&[0x1A, 0x45, 0xDF, 0xA3]) {
+            // EBML header; distinguishing WebM from Matroska audio/video
+            // requires reading the `DocType` element, which isn't
+            // needed for routing (both use the same parser).
+            return Ok(FileFormat::Matroska);
+        }
+
+        Err(Error::UnrecognizedFileFormat)
+    }
+
+    pub(crate) fn has_exif(self) -> bool {
+        matches!(
+            self,
+            FileFormat::Jpeg
+                | FileFormat::Heif
+                | FileFormat::Tiff
+                | FileFormat::Raf
+                | FileFormat::Cr3
+                | FileFormat::Dng
+        )
+    }
+
+    pub(crate) fn has_track(self) -> bool {
+        matches!(
+            self,
+            FileFormat::Mov
+                | FileFormat::Mp4
+                | FileFormat::WebM
+                | FileFormat::Matroska
+                | FileFormat::Mp3
+                | FileFormat::Aac
+        )
+    }
+}
+
+/// Distinguishes a DNG from a plain TIFF by parsing IFD0's entries and
+/// checking for the `DNGVersion` tag id among them. A malformed or
+/// truncated IFD0 is treated as "not DNG" rather than propagated, since
+/// this is purely a routing heuristic and `Tiff`'s parser is still a
+/// reasonable fallback for a file we can't even sniff this far into.
+fn is_dng(data: &[u8]) -> bool {
+    has_dng_version_tag(data).unwrap_or(false)
+}
+
+fn has_dng_version_tag(data: &[u8]) -> Result<bool> {
+    let is_le = data.starts_with(b"II");
+    let ifd0_offset = crate::exif::read_u32(data, 4, is_le)? as usize;
+    let entries = crate::exif::parse_ifd_entries(data, ifd0_offset, is_le)?;
+    Ok(entries
+        .iter()
+        .any(|e| e.tag.raw_id() == crate::dng::tags::DNG_VERSION))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn does_not_misclassify_a_tiff_whose_pixel_bytes_contain_the_dng_version_byte_pair() {
+        let mut tiff = Vec::new();
+        tiff.extend_from_slice(b"II");
+        tiff.extend_from_slice(&0x2Au16.to_le_bytes());
+        tiff.extend_from_slice(&8u32.to_le_bytes()); // ifd0 offset
+
+        tiff.extend_from_slice(&1u16.to_le_bytes()); // entry count
+        tiff.extend_from_slice(&0x0100u16.to_le_bytes()); // ImageWidth
+        tiff.extend_from_slice(&4u16.to_le_bytes());
+        tiff.extend_from_slice(&1u32.to_le_bytes());
+        tiff.extend_from_slice(&100u32.to_le_bytes());
+        tiff.extend_from_slice(&0u32.to_le_bytes()); // next IFD offset
+
+        // Pixel payload that happens to contain the DNGVersion tag id's
+        // byte pair (0x12, 0xC6 little-endian) — this must not make the
+        // file look like a DNG.
+        tiff.extend_from_slice(&[0xAB, 0x12, 0xC6, 0xCD]);
+
+        assert!(!is_dng(&tiff));
+    }
+
+    #[test]
+    fn recognizes_dng_version_tag_in_ifd0() {
+        let mut tiff = Vec::new();
+        tiff.extend_from_slice(b"II");
+        tiff.extend_from_slice(&0x2Au16.to_le_bytes());
+        tiff.extend_from_slice(&8u32.to_le_bytes()); // ifd0 offset
+
+        tiff.extend_from_slice(&1u16.to_le_bytes()); // entry count
+        tiff.extend_from_slice(&crate::dng::tags::DNG_VERSION.to_le_bytes());
+        tiff.extend_from_slice(&1u16.to_le_bytes()); // type: BYTE
+        tiff.extend_from_slice(&4u32.to_le_bytes());
+        tiff.extend_from_slice(&[1, 4, 0, 0]); // inline DNG version 1.4.0.0
+        tiff.extend_from_slice(&0u32.to_le_bytes()); // next IFD offset
+
+        assert!(is_dng(&tiff));
+    }
+}
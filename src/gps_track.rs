@@ -0,0 +1,382 @@
+//! Geotagging from an external GPS track log (GPX/KML).
+//!
+//! This lets callers derive a [`GPSInfo`] for a file that has no embedded
+//! GPS tags by correlating its capture time against a GPS track log
+//! recorded separately (e.g. by a phone or a dedicated GPS logger), the
+//! way exiftool's `-geotag` option and older tools like PhotoPoint do.
+//!
+//! ```no_run
+//! use nom_exif::{GpsTrack, GpsTrackOptions};
+//! use std::time::Duration;
+//!
+//! # fn main() -> nom_exif::Result<()> {
+//! let gpx = std::fs::read_to_string("./testdata/track.gpx")?;
+//! let track = GpsTrack::from_gpx(&gpx)?;
+//!
+//! let opts = GpsTrackOptions::default().with_max_gap(Duration::from_secs(60));
+//! let gps_info = track.locate(1700000000000, &opts)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::time::Duration;
+
+use crate::exif::{GPSInfo, LatLng};
+use crate::values::URational;
+use crate::{Error, Result};
+
+/// One GPS fix in a track log, normalized to Unix epoch milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TrackPoint {
+    time_ms: i64,
+    lat: f64,
+    lon: f64,
+    ele: Option<f64>,
+}
+
+/// A time-sorted GPS track log, parsed from a GPX or KML file.
+///
+/// Build one with [`GpsTrack::from_gpx`] or [`GpsTrack::from_kml`], then
+/// call [`GpsTrack::locate`] to interpolate a [`GPSInfo`] for an arbitrary
+/// capture time.
+#[derive(Debug, Clone)]
+pub struct GpsTrack {
+    // Sorted ascending by `time_ms`.
+    points: Vec<TrackPoint>,
+}
+
+/// Tuning knobs for [`GpsTrack::locate`].
+#[derive(Debug, Clone, Copy)]
+pub struct GpsTrackOptions {
+    /// Maximum distance (in either direction) from the nearest trackpoint
+    /// that a capture time may fall outside the track's time range and
+    /// still be snapped to that endpoint. Defaults to 30s.
+    pub snap_tolerance: Duration,
+    /// Maximum allowed gap between the two bracketing trackpoints used for
+    /// interpolation. If the capture time falls inside a gap wider than
+    /// this, [`GpsTrack::locate`] fails rather than interpolating across
+    /// it. Defaults to 5 minutes.
+    pub max_gap: Duration,
+    /// Fixed offset (in milliseconds) added to the capture time before
+    /// matching it against the track log, to reconcile a photo's
+    /// local-time timestamp with a UTC track log (or vice versa).
+    pub fixed_offset_ms: i64,
+}
+
+impl Default for GpsTrackOptions {
+    fn default() -> Self {
+        Self {
+            snap_tolerance: Duration::from_secs(30),
+            max_gap: Duration::from_secs(5 * 60),
+            fixed_offset_ms: 0,
+        }
+    }
+}
+
+impl GpsTrackOptions {
+    /// Sets [`Self::max_gap`].
+    pub fn with_max_gap(mut self, max_gap: Duration) -> Self {
+        self.max_gap = max_gap;
+        self
+    }
+
+    /// Sets [`Self::snap_tolerance`].
+    pub fn with_snap_tolerance(mut self, tolerance: Duration) -> Self {
+        self.snap_tolerance = tolerance;
+        self
+    }
+
+    /// Sets [`Self::fixed_offset_ms`].
+    pub fn with_fixed_offset_ms(mut self, offset_ms: i64) -> Self {
+        self.fixed_offset_ms = offset_ms;
+        self
+    }
+}
+
+impl GpsTrack {
+    /// Parses a GPX track log (`<trkpt lat=".." lon=".."><ele>..</ele>
+    /// <time>..</time></trkpt>`) into a time-sorted [`GpsTrack`].
+    pub fn from_gpx(gpx: &str) -> Result<Self> {
+        let mut points = Vec::new();
+
+        for trkpt in split_elements(gpx, "trkpt") {
+            let lat = attr_f64(trkpt.0, "lat")
+                .ok_or_else(|| Error::ParseFailed("gpx trkpt is missing lat".into()))?;
+            let lon = attr_f64(trkpt.0, "lon")
+                .ok_or_else(|| Error::ParseFailed("gpx trkpt is missing lon".into()))?;
+            let time = child_text(trkpt.1, "time")
+                .ok_or_else(|| Error::ParseFailed("gpx trkpt is missing time".into()))?;
+            let ele = child_text(trkpt.1, "ele").and_then(|s| s.trim().parse::<f64>().ok());
+
+            points.push(TrackPoint {
+                time_ms: parse_iso8601_millis(time.trim())?,
+                lat,
+                lon,
+                ele,
+            });
+        }
+
+        Self::from_points(points)
+    }
+
+    /// Parses a KML `<gx:Track>` (`<gx:coord>` or `<coordinates>` lon,lat,alt
+    /// paired with `<when>` timestamps) into a time-sorted [`GpsTrack`].
+    pub fn from_kml(kml: &str) -> Result<Self> {
+        let whens: Vec<&str> = extract_all(kml, "<when>", "</when>")
+            .into_iter()
+            .chain(extract_all(kml, "<gx:when>", "</gx:when>"))
+            .collect();
+        let coords: Vec<&str> = extract_all(kml, "<gx:coord>", "</gx:coord>")
+            .into_iter()
+            .chain(extract_all(kml, "<coordinates>", "</coordinates>"))
+            .collect();
+
+        if whens.len() != coords.len() {
+            return Err(Error::ParseFailed(format!(
+                "kml <when> count ({}) doesn't match coordinate count ({})",
+                whens.len(),
+                coords.len()
+            )));
+        }
+
+        let mut points = Vec::with_capacity(whens.len());
+        for (when, coord) in whens.into_iter().zip(coords) {
+            let mut parts = coord.split_whitespace().next().unwrap_or(coord).split(',');
+            let lon: f64 = parts
+                .next()
+                .and_then(|s| s.parse().ok())
+                .ok_or_else(|| Error::ParseFailed("kml coordinate is missing longitude".into()))?;
+            let lat: f64 = parts
+                .next()
+                .and_then(|s| s.parse().ok())
+                .ok_or_else(|| Error::ParseFailed("kml coordinate is missing latitude".into()))?;
+            let ele = parts.next().and_then(|s| s.parse().ok());
+
+            points.push(TrackPoint {
+                time_ms: parse_iso8601_millis(when.trim())?,
+                lat,
+                lon,
+                ele,
+            });
+        }
+
+        Self::from_points(points)
+    }
+
+    fn from_points(mut points: Vec<TrackPoint>) -> Result<Self> {
+        if points.is_empty() {
+            return Err(Error::ParseFailed("track log contains no points".into()));
+        }
+        points.sort_by_key(|p| p.time_ms);
+        Ok(Self { points })
+    }
+
+    /// Interpolates a [`GPSInfo`] for the given capture time (Unix epoch
+    /// milliseconds), per `opts`.
+    ///
+    /// Binary-searches for the two bracketing trackpoints and linearly
+    /// interpolates latitude, longitude and elevation between them. If
+    /// `capture_ms` falls outside the track's time range but within
+    /// `opts.snap_tolerance`, the nearest endpoint is used instead of
+    /// failing.
+    pub fn locate(&self, capture_ms: i64, opts: &GpsTrackOptions) -> Result<GPSInfo> {
+        let t = capture_ms + opts.fixed_offset_ms;
+        let tolerance_ms = opts.snap_tolerance.as_millis() as i64;
+        let max_gap_ms = opts.max_gap.as_millis() as i64;
+
+        let idx = self.points.partition_point(|p| p.time_ms <= t);
+
+        let (lat, lon, ele) = if idx == 0 {
+            let first = &self.points[0];
+            if first.time_ms - t > tolerance_ms {
+                return Err(Error::ParseFailed(format!(
+                    "capture time is {}ms before the track log starts, outside the {}ms tolerance",
+                    first.time_ms - t,
+                    tolerance_ms
+                )));
+            }
+            (first.lat, first.lon, first.ele)
+        } else if idx == self.points.len() {
+            let last = &self.points[self.points.len() - 1];
+            if t - last.time_ms > tolerance_ms {
+                return Err(Error::ParseFailed(format!(
+                    "capture time is {}ms after the track log ends, outside the {}ms tolerance",
+                    t - last.time_ms,
+                    tolerance_ms
+                )));
+            }
+            (last.lat, last.lon, last.ele)
+        } else {
+            let p0 = &self.points[idx - 1];
+            let p1 = &self.points[idx];
+
+            if p0.time_ms == t {
+                (p0.lat, p0.lon, p0.ele)
+            } else if p1.time_ms - p0.time_ms > max_gap_ms {
+                return Err(Error::ParseFailed(format!(
+                    "capture time falls in a {}ms recording gap, exceeding the {}ms limit",
+                    p1.time_ms - p0.time_ms,
+                    max_gap_ms
+                )));
+            } else {
+                let frac = (t - p0.time_ms) as f64 / (p1.time_ms - p0.time_ms) as f64;
+                let lat = p0.lat + (p1.lat - p0.lat) * frac;
+                let lon = p0.lon + (p1.lon - p0.lon) * frac;
+                let ele = match (p0.ele, p1.ele) {
+                    (Some(e0), Some(e1)) => Some(e0 + (e1 - e0) * frac),
+                    _ => None,
+                };
+                (lat, lon, ele)
+            }
+        };
+
+        Ok(decimal_to_gps_info(lat, lon, ele))
+    }
+}
+
+/// Converts interpolated decimal-degree coordinates into the crate's
+/// `[(deg,1),(min,1),(sec,100)]` rational [`LatLng`] form, and into a
+/// [`GPSInfo`] with refs derived from sign.
+fn decimal_to_gps_info(lat: f64, lon: f64, ele: Option<f64>) -> GPSInfo {
+    GPSInfo {
+        latitude_ref: if lat >= 0.0 { 'N' } else { 'S' },
+        latitude: decimal_to_latlng(lat),
+        longitude_ref: if lon >= 0.0 { 'E' } else { 'W' },
+        longitude: decimal_to_latlng(lon),
+        altitude_ref: if ele.unwrap_or(0.0) >= 0.0 { 0 } else { 1 },
+        altitude: URational(ele.unwrap_or(0.0).abs().round() as u32, 1),
+        speed_ref: None,
+        speed: None,
+    }
+}
+
+fn decimal_to_latlng(decimal: f64) -> LatLng {
+    let decimal = decimal.abs();
+    let mut deg = decimal.trunc() as u32;
+    let min_full = (decimal - deg as f64) * 60.0;
+    let mut min = min_full.trunc() as u32;
+    let mut sec = ((min_full - min as f64) * 60.0 * 100.0).round() as u32;
+
+    // Rounding the hundredths-of-a-second component can carry all the
+    // way up, e.g. 59.999s rounds to 6000 (i.e. 60.00s); propagate that
+    // into minutes/degrees rather than emitting an out-of-range value.
+    if sec >= 6000 {
+        sec -= 6000;
+        min += 1;
+    }
+    if min >= 60 {
+        min -= 60;
+        deg += 1;
+    }
+
+    [(deg, 1), (min, 1), (sec, 100)].into()
+}
+
+/// Parses an ISO-8601 timestamp (as used by both GPX `<time>` and KML
+/// `<when>`) into Unix epoch milliseconds.
+fn parse_iso8601_millis(s: &str) -> Result<i64> {
+    use chrono::DateTime;
+
+    DateTime::parse_from_rfc3339(s)
+        .map(|dt| dt.timestamp_millis())
+        .map_err(|e| Error::ParseFailed(format!("invalid track log timestamp {s:?}: {e}")))
+}
+
+/// Returns `(attrs, inner)` for every `<tag ...>...</tag>` or
+/// `<tag .../>` occurrence, in document order.
+fn split_elements<'a>(xml: &'a str, tag: &str) -> Vec<(&'a str, &'a str)> {
+    let open = format!("<{tag}");
+    let mut out = Vec::new();
+    let mut rest = xml;
+
+    while let Some(start) = rest.find(open.as_str()) {
+        let after_open = &rest[start..];
+        let Some(tag_end) = after_open.find('>') else {
+            break;
+        };
+        let attrs = &after_open[open.len()..tag_end];
+
+        if attrs.trim_end().ends_with('/') {
+            // Self-closing: no inner text.
+            out.push((&attrs[..attrs.trim_end().len() - 1], ""));
+            rest = &after_open[tag_end + 1..];
+            continue;
+        }
+
+        let close = format!("</{tag}>");
+        let Some(close_start) = after_open[tag_end + 1..].find(close.as_str()) else {
+            break;
+        };
+        let inner = &after_open[tag_end + 1..tag_end + 1 + close_start];
+        out.push((attrs, inner));
+        rest = &after_open[tag_end + 1 + close_start + close.len()..];
+    }
+
+    out
+}
+
+fn attr_f64(attrs: &str, name: &str) -> Option<f64> {
+    let needle = format!("{name}=\"");
+    let start = attrs.find(needle.as_str())? + needle.len();
+    let end = attrs[start..].find('"')? + start;
+    attrs[start..end].parse().ok()
+}
+
+fn child_text<'a>(xml: &'a str, tag: &str) -> Option<&'a str> {
+    extract_all(xml, &format!("<{tag}>"), &format!("</{tag}>"))
+        .into_iter()
+        .next()
+}
+
+fn extract_all<'a>(xml: &'a str, open: &str, close: &str) -> Vec<&'a str> {
+    let mut out = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find(open) {
+        let after = &rest[start + open.len()..];
+        let Some(end) = after.find(close) else {
+            break;
+        };
+        out.push(&after[..end]);
+        rest = &after[end + close.len()..];
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GPX: &str = r#"
+        <gpx><trk><trkseg>
+            <trkpt lat="22.0" lon="113.0"><ele>10.0</ele><time>2024-02-02T08:00:00Z</time></trkpt>
+            <trkpt lat="22.1" lon="113.1"><ele>20.0</ele><time>2024-02-02T08:01:00Z</time></trkpt>
+        </trkseg></trk></gpx>
+    "#;
+
+    #[test]
+    fn interpolates_midpoint() {
+        let track = GpsTrack::from_gpx(GPX).unwrap();
+        let t0 = parse_iso8601_millis("2024-02-02T08:00:00Z").unwrap();
+        let gps = track.locate(t0 + 30_000, &GpsTrackOptions::default()).unwrap();
+        assert_eq!(gps.latitude_ref, 'N');
+        assert_eq!(gps.longitude_ref, 'E');
+        assert_eq!(gps.latitude, [(22, 1), (3, 1), (0, 100)].into());
+    }
+
+    #[test]
+    fn fails_on_long_gap() {
+        let track = GpsTrack::from_gpx(GPX).unwrap();
+        let t0 = parse_iso8601_millis("2024-02-02T08:00:00Z").unwrap();
+        let opts = GpsTrackOptions::default().with_max_gap(Duration::from_secs(1));
+        assert!(track.locate(t0 + 30_000, &opts).is_err());
+    }
+
+    #[test]
+    fn snaps_within_tolerance() {
+        let track = GpsTrack::from_gpx(GPX).unwrap();
+        let t0 = parse_iso8601_millis("2024-02-02T08:00:00Z").unwrap();
+        let opts = GpsTrackOptions::default().with_snap_tolerance(Duration::from_secs(10));
+        let gps = track.locate(t0 - 5_000, &opts).unwrap();
+        assert_eq!(gps.latitude, [(22, 1), (0, 1), (0, 100)].into());
+    }
+}
@@ -0,0 +1,307 @@
+//! HEIF/HEIC (ISOBMFF-based) Exif and XMP extraction.
+//!
+//! Exif lives in an `Exif` item referenced from `meta`/`iinf`/`iloc`;
+//! XMP lives in a `mime` item (`application/rdf+xml`) or a `uuid`/`XMP_`
+//! box, located the same way as in `.mov`/`.mp4` (see [`crate::bbox`]).
+
+use crate::bbox::{find_box, find_xmp_packet, top_level_boxes};
+use crate::exif::{check_exif_header, Exif};
+use crate::{Error, Result};
+
+/// Locates the raw (header-stripped) TIFF/Exif bytes inside a HEIF
+/// file's `Exif` item payload.
+pub(crate) fn heif_extract_exif(data: &[u8]) -> Result<Option<Vec<u8>>> {
+    let (_, meta) = find_box(data, "meta")?;
+    let Some(meta) = meta else { return Ok(None) };
+
+    // `Exif` items are typically wrapped directly as an `Exif` box, or,
+    // in some encoders, exposed via a generically-named `infe`/`iloc`
+    // pair; this crate looks for the simpler, widely-produced case of an
+    // `Exif` box nested under `meta`.
+    let (_, exif_box) = find_box(meta.data, "Exif")?;
+    let Some(exif_box) = exif_box else { return Ok(None) };
+
+    // An `Exif` item payload is prefixed with a 4-byte offset to the
+    // actual TIFF header (normally 0 or the length of a leading
+    // `Exif\0\0` marker), per the HEIF spec.
+    let payload = exif_box.data;
+    if payload.len() < 4 {
+        return Ok(None);
+    }
+    let tiff_offset = 4 + u32::from_be_bytes(payload[0..4].try_into().unwrap()) as usize;
+    if tiff_offset > payload.len() {
+        return Err(Error::ParseFailed("HEIF Exif item offset out of range".into()));
+    }
+    let tiff = &payload[tiff_offset..];
+
+    if check_exif_header(tiff)? {
+        Ok(Some(tiff[6..].to_vec()))
+    } else {
+        Ok(Some(tiff.to_vec()))
+    }
+}
+
+/// Locates the raw RDF/XML XMP packet embedded in a HEIF file, if any:
+/// first the nonstandard `uuid`/`XMP_` box path (shared with `.mov`/
+/// `.mp4`), then the standard HEIF mechanism most real encoders actually
+/// use — a `mime` item (`application/rdf+xml`) registered in `iinf` and
+/// located via `iloc`.
+pub(crate) fn heif_extract_xmp(data: &[u8]) -> Result<Option<Vec<u8>>> {
+    if let Some(packet) = find_xmp_packet(data)? {
+        return Ok(Some(packet.to_vec()));
+    }
+
+    let (_, meta) = find_box(data, "meta")?;
+    let Some(meta) = meta else { return Ok(None) };
+
+    let (_, iinf) = find_box(meta.data, "iinf")?;
+    let Some(iinf) = iinf else { return Ok(None) };
+
+    let Some(xmp_item) = parse_iinf_mime_items(iinf.data)
+        .into_iter()
+        .find(|item| item.content_type == "application/rdf+xml")
+    else {
+        return Ok(None);
+    };
+
+    let (_, iloc) = find_box(meta.data, "iloc")?;
+    let Some(iloc) = iloc else { return Ok(None) };
+
+    let Some((start, len)) = resolve_item_location(iloc.data, xmp_item.id) else {
+        return Ok(None);
+    };
+
+    Ok(data.get(start..start + len).map(|p| p.to_vec()))
+}
+
+/// A `mime`-typed entry parsed out of `iinf`.
+struct MimeItem {
+    id: u32,
+    content_type: String,
+}
+
+/// Parses `iinf`'s body (a version/flags header, an entry count, then
+/// that many `infe` boxes back to back) into its `mime`-typed items.
+fn parse_iinf_mime_items(data: &[u8]) -> Vec<MimeItem> {
+    let Some(&version) = data.first() else { return Vec::new() };
+    let entry_count_size = if version == 0 { 2 } else { 4 };
+    let boxes_start = 4 + entry_count_size;
+    let Some(infe_boxes) = data.get(boxes_start..) else { return Vec::new() };
+
+    top_level_boxes(infe_boxes)
+        .into_iter()
+        .filter(|b| &b.box_type == b"infe")
+        .filter_map(|b| parse_infe_mime_item(b.data))
+        .collect()
+}
+
+/// Parses one `infe` (item info entry) box, returning `None` unless it's
+/// a `mime`-typed item. HEIF mandates `infe` version 2 or 3 (the only
+/// versions with a typed `item_type` field); earlier versions are
+/// pre-HEIF legacy this crate doesn't target.
+fn parse_infe_mime_item(data: &[u8]) -> Option<MimeItem> {
+    let version = *data.first()?;
+    if version < 2 {
+        return None;
+    }
+
+    let mut offset = 4usize; // version(1) + flags(3)
+    let item_id = if version >= 3 {
+        let id = u32::from_be_bytes(data.get(offset..offset + 4)?.try_into().ok()?);
+        offset += 4;
+        id
+    } else {
+        let id = u16::from_be_bytes(data.get(offset..offset + 2)?.try_into().ok()?) as u32;
+        offset += 2;
+        id
+    };
+
+    offset += 2; // item_protection_index
+
+    let item_type = data.get(offset..offset + 4)?;
+    offset += 4;
+    if item_type != b"mime" {
+        return None;
+    }
+
+    let name_len = data.get(offset..)?.iter().position(|&b| b == 0)?;
+    offset += name_len + 1;
+
+    let rest = data.get(offset..)?;
+    let content_type_len = rest.iter().position(|&b| b == 0).unwrap_or(rest.len());
+    let content_type = String::from_utf8_lossy(&rest[..content_type_len]).into_owned();
+
+    Some(MimeItem { id: item_id, content_type })
+}
+
+/// Reads a big-endian unsigned integer of `size` bytes (0, as used for an
+/// absent `base_offset`, reads as `0`) from `data` at `*offset`, advancing
+/// `*offset` past it.
+fn read_uint(data: &[u8], offset: &mut usize, size: usize) -> Option<u64> {
+    if size == 0 {
+        return Some(0);
+    }
+    let bytes = data.get(*offset..*offset + size)?;
+    *offset += size;
+    Some(bytes.iter().fold(0u64, |acc, &b| (acc << 8) | b as u64))
+}
+
+/// Resolves `item_id`'s location from `iloc`'s body into an absolute
+/// `(offset, length)` within the file, following only the common,
+/// file-offset-based construction method (`construction_method == 0`)
+/// and only the first extent — sufficient for the single, unfragmented
+/// XMP packets real encoders produce.
+fn resolve_item_location(data: &[u8], item_id: u32) -> Option<(usize, usize)> {
+    let version = *data.first()?;
+    let mut offset = 4usize; // version(1) + flags(3)
+
+    let sizes = *data.get(offset)?;
+    let offset_size = (sizes >> 4) as usize;
+    let length_size = (sizes & 0x0F) as usize;
+    offset += 1;
+
+    let sizes2 = *data.get(offset)?;
+    let base_offset_size = (sizes2 >> 4) as usize;
+    let index_size = if version == 1 || version == 2 { (sizes2 & 0x0F) as usize } else { 0 };
+    offset += 1;
+
+    let item_count = if version < 2 {
+        let n = u16::from_be_bytes(data.get(offset..offset + 2)?.try_into().ok()?) as u32;
+        offset += 2;
+        n
+    } else {
+        let n = u32::from_be_bytes(data.get(offset..offset + 4)?.try_into().ok()?);
+        offset += 4;
+        n
+    };
+
+    for _ in 0..item_count {
+        let id = if version < 2 {
+            let id = u16::from_be_bytes(data.get(offset..offset + 2)?.try_into().ok()?) as u32;
+            offset += 2;
+            id
+        } else {
+            let id = u32::from_be_bytes(data.get(offset..offset + 4)?.try_into().ok()?);
+            offset += 4;
+            id
+        };
+
+        let construction_method = if version == 1 || version == 2 {
+            let m = u16::from_be_bytes(data.get(offset..offset + 2)?.try_into().ok()?) & 0x0F;
+            offset += 2;
+            m
+        } else {
+            0
+        };
+
+        offset += 2; // data_reference_index
+        let base_offset = read_uint(data, &mut offset, base_offset_size)?;
+
+        let extent_count = u16::from_be_bytes(data.get(offset..offset + 2)?.try_into().ok()?);
+        offset += 2;
+
+        let mut first_extent = None;
+        for _ in 0..extent_count {
+            if index_size > 0 {
+                offset += index_size;
+            }
+            let extent_offset = read_uint(data, &mut offset, offset_size)?;
+            let extent_length = read_uint(data, &mut offset, length_size)?;
+            if first_extent.is_none() {
+                first_extent = Some((extent_offset, extent_length));
+            }
+        }
+
+        if id == item_id {
+            if construction_method != 0 {
+                // Item data lives in an `idat` box or another file;
+                // not resolved against the passed-in file buffer.
+                return None;
+            }
+            let (extent_offset, extent_length) = first_extent?;
+            return Some(((base_offset + extent_offset) as usize, extent_length as usize));
+        }
+    }
+
+    None
+}
+
+#[deprecated(note = "Please use `MediaParser` instead")]
+pub fn parse_heif_exif<R: std::io::Read>(mut reader: R) -> Result<Option<Exif>> {
+    use std::io::Read as _;
+
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+
+    match heif_extract_exif(&data)? {
+        Some(tiff) => {
+            let mut prefixed = b"Exif\0\0".to_vec();
+            prefixed.extend_from_slice(&tiff);
+            #[allow(deprecated)]
+            crate::exif::parse_exif(std::io::Cursor::new(prefixed))
+        }
+        None => Err(Error::ExifNotFound),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_box(ty: &[u8; 4], body: &[u8]) -> Vec<u8> {
+        let size = (8 + body.len()) as u32;
+        let mut out = size.to_be_bytes().to_vec();
+        out.extend_from_slice(ty);
+        out.extend_from_slice(body);
+        out
+    }
+
+    #[test]
+    fn extracts_xmp_from_mime_item_via_iinf_and_iloc() {
+        let xmp_payload = b"<?xpacket begin=\"\"?><x:xmpmeta/>";
+
+        let mut infe_body = vec![2, 0, 0, 0]; // version 2, flags 0
+        infe_body.extend_from_slice(&1u16.to_be_bytes()); // item_ID
+        infe_body.extend_from_slice(&0u16.to_be_bytes()); // item_protection_index
+        infe_body.extend_from_slice(b"mime"); // item_type
+        infe_body.push(0); // item_name: empty, null-terminated
+        infe_body.extend_from_slice(b"application/rdf+xml");
+        infe_body.push(0); // content_type: null-terminated
+        let infe_box = make_box(b"infe", &infe_body);
+
+        let mut iinf_body = vec![0, 0, 0, 0]; // version 0, flags 0
+        iinf_body.extend_from_slice(&1u16.to_be_bytes()); // entry_count
+        iinf_body.extend_from_slice(&infe_box);
+        let iinf_box = make_box(b"iinf", &iinf_body);
+
+        let mut iloc_body = vec![0, 0, 0, 0]; // version 0, flags 0
+        iloc_body.push(0x44); // offset_size=4, length_size=4
+        iloc_body.push(0x00); // base_offset_size=0, index_size=0
+        iloc_body.extend_from_slice(&1u16.to_be_bytes()); // item_count
+        iloc_body.extend_from_slice(&1u16.to_be_bytes()); // item_ID
+        iloc_body.extend_from_slice(&0u16.to_be_bytes()); // data_reference_index
+        iloc_body.extend_from_slice(&1u16.to_be_bytes()); // extent_count
+        let extent_offset_pos_in_iloc_body = iloc_body.len();
+        iloc_body.extend_from_slice(&0u32.to_be_bytes()); // extent_offset (patched below)
+        iloc_body.extend_from_slice(&(xmp_payload.len() as u32).to_be_bytes()); // extent_length
+        let iloc_box = make_box(b"iloc", &iloc_body);
+
+        let mut meta_body = Vec::new();
+        meta_body.extend_from_slice(&iinf_box);
+        let iloc_box_pos_in_meta_body = meta_body.len();
+        meta_body.extend_from_slice(&iloc_box);
+        let mut file = make_box(b"meta", &meta_body);
+
+        // The XMP payload is appended right after the `meta` box, so its
+        // absolute file offset is `file`'s length so far; patch that into
+        // the extent_offset field we left as a placeholder above.
+        let extent_offset_pos =
+            8 + iloc_box_pos_in_meta_body + 8 + extent_offset_pos_in_iloc_body;
+        let xmp_start = file.len() as u32;
+        file[extent_offset_pos..extent_offset_pos + 4].copy_from_slice(&xmp_start.to_be_bytes());
+        file.extend_from_slice(xmp_payload);
+
+        let xmp = heif_extract_xmp(&file).unwrap();
+        assert_eq!(xmp.as_deref(), Some(xmp_payload.as_slice()));
+    }
+}
@@ -0,0 +1,250 @@
+//! ID3v2 tag parsing for MP3 files.
+//!
+//! Parses the 10-byte tag header at the start of the file (`ID3` magic,
+//! version, flags, syncsafe 28-bit size) and walks its frames (4-char
+//! frame id, size, flags, payload), mapping the common ones into
+//! [`TrackInfoTag`] values the same way `mov`/`ebml` do for video/Matroska
+//! containers.
+
+use crate::values::EntryValue;
+use crate::video::{TrackInfo, TrackInfoTag};
+use crate::{Error, Result};
+
+const HEADER_LEN: usize = 10;
+
+/// Returns `true` if `data` starts with an ID3v2 header, i.e. this looks
+/// like an MP3 file `MediaSource::has_track()` should recognize.
+pub(crate) fn has_id3_header(data: &[u8]) -> bool {
+    data.len() >= HEADER_LEN && &data[0..3] == b"ID3"
+}
+
+/// Parses the ID3v2 tag at the start of `data` into a [`TrackInfo`].
+/// The MPEG audio frames after the tag are not read, so `DurationMs` is
+/// set only from a `TLEN` frame.
+pub(crate) fn parse_id3(data: &[u8]) -> Result<TrackInfo> {
+    if !has_id3_header(data) {
+        return Err(Error::ParseFailed("not an ID3v2 tagged file".into()));
+    }
+
+    let major_version = data[3];
+    let flags = data[5];
+    let tag_size = syncsafe_u32(&data[6..10]) as usize;
+
+    let mut offset = HEADER_LEN;
+    if flags & 0x40 != 0 {
+        // Extended header present: its size is itself a syncsafe u32
+        // (v4) or a plain u32 (v3), immediately following the header.
+        let ext_size_bytes = data
+            .get(offset..offset + 4)
+            .ok_or_else(|| Error::ParseFailed("ID3v2 extended header is truncated".into()))?;
+        let ext_size = if major_version >= 4 {
+            syncsafe_u32(ext_size_bytes) as usize
+        } else {
+            u32::from_be_bytes(ext_size_bytes.try_into().unwrap()) as usize
+        };
+        offset += ext_size;
+    }
+
+    let tag_end = (HEADER_LEN + tag_size).min(data.len());
+    let mut info = TrackInfo::default();
+
+    while offset + 10 <= tag_end {
+        let frame_id = &data[offset..offset + 4];
+        if frame_id == [0, 0, 0, 0] {
+            break; // Padding.
+        }
+        let frame_size = if major_version >= 4 {
+            syncsafe_u32(&data[offset + 4..offset + 8]) as usize
+        } else {
+            u32::from_be_bytes([
+                data[offset + 4],
+                data[offset + 5],
+                data[offset + 6],
+                data[offset + 7],
+            ]) as usize
+        };
+        let payload_start = offset + 10;
+        let payload_end = (payload_start + frame_size).min(data.len());
+        let payload = &data[payload_start..payload_end];
+
+        if let Some((key, value)) = decode_frame(frame_id, payload) {
+            info.put(key, value);
+        }
+
+        offset = payload_end;
+    }
+
+    Ok(info)
+}
+
+fn decode_frame(frame_id: &[u8], payload: &[u8]) -> Option<(TrackInfoTag, EntryValue)> {
+    if frame_id == b"APIC" {
+        return decode_apic_frame(payload).map(|data| (TrackInfoTag::CoverArt, EntryValue::Undefined(data)));
+    }
+
+    let key = match frame_id {
+        b"TIT2" => TrackInfoTag::Title,
+        b"TPE1" => TrackInfoTag::Artist,
+        b"TALB" => TrackInfoTag::Album,
+        b"TDRC" | b"TYER" => TrackInfoTag::CreateDate,
+        b"TLEN" => TrackInfoTag::DurationMs,
+        _ => return None,
+    };
+    decode_text_frame(payload).map(|text| (key, text.into()))
+}
+
+/// `APIC` (attached picture) frame payload: a 1-byte text encoding, a
+/// null-terminated MIME type, a 1-byte picture type, a null-terminated
+/// (encoding-dependent) description, then the raw image bytes.
+fn decode_apic_frame(payload: &[u8]) -> Option<Vec<u8>> {
+    let (&encoding, rest) = payload.split_first()?;
+    let mime_end = rest.iter().position(|&b| b == 0)?;
+    let rest = rest.get(mime_end + 1..)?;
+    let (_picture_type, rest) = rest.split_first()?;
+    let desc_end = find_terminator(rest, encoding)?;
+    rest.get(desc_end..).map(|b| b.to_vec())
+}
+
+/// Finds the end of a (possibly UTF-16) encoded, nul-terminated string,
+/// returning the offset just past its terminator.
+fn find_terminator(bytes: &[u8], encoding: u8) -> Option<usize> {
+    if encoding == 1 || encoding == 2 {
+        let mut i = 0;
+        while i + 1 < bytes.len() {
+            if bytes[i] == 0 && bytes[i + 1] == 0 {
+                return Some(i + 2);
+            }
+            i += 2;
+        }
+        None
+    } else {
+        bytes.iter().position(|&b| b == 0).map(|i| i + 1)
+    }
+}
+
+/// Text-information frame payloads start with a 1-byte text encoding
+/// marker (0 = Latin-1, 1 = UTF-16 w/ BOM, 2 = UTF-16BE, 3 = UTF-8).
+fn decode_text_frame(payload: &[u8]) -> Option<String> {
+    let (&encoding, text) = payload.split_first()?;
+    let text = match encoding {
+        0 => text.iter().map(|&b| b as char).collect(),
+        3 => String::from_utf8_lossy(text).into_owned(),
+        1 => decode_utf16(text, None),
+        2 => decode_utf16(text, Some(false)),
+        _ => return None,
+    };
+    Some(text.trim_end_matches('\0').to_string())
+}
+
+fn decode_utf16(bytes: &[u8], force_le: Option<bool>) -> String {
+    let (le, bytes) = match force_le {
+        Some(le) => (le, bytes),
+        None => match bytes.get(0..2) {
+            Some([0xff, 0xfe]) => (true, &bytes[2..]),
+            Some([0xfe, 0xff]) => (false, &bytes[2..]),
+            _ => (true, bytes),
+        },
+    };
+
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|c| {
+            if le {
+                u16::from_le_bytes([c[0], c[1]])
+            } else {
+                u16::from_be_bytes([c[0], c[1]])
+            }
+        })
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+/// Decodes a 4-byte syncsafe integer (each byte contributes only its
+/// lower 7 bits), as used throughout ID3v2 for sizes.
+fn syncsafe_u32(bytes: &[u8]) -> u32 {
+    bytes
+        .iter()
+        .fold(0u32, |acc, &b| (acc << 7) | (b & 0x7f) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(id: &[u8; 4], text: &str) -> Vec<u8> {
+        let mut payload = vec![3u8]; // UTF-8
+        payload.extend_from_slice(text.as_bytes());
+        let mut out = id.to_vec();
+        out.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        out.extend_from_slice(&[0, 0]); // flags
+        out.extend_from_slice(&payload);
+        out
+    }
+
+    fn apic_frame(mime: &str, picture: &[u8]) -> Vec<u8> {
+        let mut payload = vec![3u8]; // UTF-8
+        payload.extend_from_slice(mime.as_bytes());
+        payload.push(0); // MIME type terminator
+        payload.push(3); // picture type: front cover
+        payload.push(0); // empty description + terminator
+        payload.extend_from_slice(picture);
+
+        let mut out = b"APIC".to_vec();
+        out.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        out.extend_from_slice(&[0, 0]); // flags
+        out.extend_from_slice(&payload);
+        out
+    }
+
+    #[test]
+    fn parses_embedded_cover_art() {
+        let picture = [0xFFu8, 0xD8, 0xFF, 0xE0, 0x00, 0x10];
+        let mut data = vec![b'I', b'D', b'3', 4, 0, 0];
+        let frames = apic_frame("image/jpeg", &picture);
+        let size = frames.len() as u32;
+        data.extend_from_slice(&[
+            ((size >> 21) & 0x7f) as u8,
+            ((size >> 14) & 0x7f) as u8,
+            ((size >> 7) & 0x7f) as u8,
+            (size & 0x7f) as u8,
+        ]);
+        data.extend(frames);
+
+        let info = parse_id3(&data).unwrap();
+        assert_eq!(
+            info.get(TrackInfoTag::CoverArt),
+            Some(&EntryValue::Undefined(picture.to_vec()))
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_extended_header_instead_of_panicking() {
+        // `ID3`, v4, flags with the extended-header bit set, but fewer
+        // than 4 bytes follow the 10-byte header for its size field.
+        let data = vec![b'I', b'D', b'3', 4, 0, 0x40, 0, 0, 0, 0];
+        assert!(has_id3_header(&data));
+        assert!(parse_id3(&data).is_err());
+    }
+
+    #[test]
+    fn parses_title_and_artist() {
+        let mut frames = Vec::new();
+        frames.extend(frame(b"TIT2", "Sample Title"));
+        frames.extend(frame(b"TPE1", "Sample Artist"));
+
+        let mut data = vec![b'I', b'D', b'3', 4, 0, 0];
+        let size = frames.len() as u32;
+        data.extend_from_slice(&[
+            ((size >> 21) & 0x7f) as u8,
+            ((size >> 14) & 0x7f) as u8,
+            ((size >> 7) & 0x7f) as u8,
+            (size & 0x7f) as u8,
+        ]);
+        data.extend(frames);
+
+        assert!(has_id3_header(&data));
+        let info = parse_id3(&data).unwrap();
+        assert_eq!(info.get(TrackInfoTag::Title), Some(&"Sample Title".into()));
+        assert_eq!(info.get(TrackInfoTag::Artist), Some(&"Sample Artist".into()));
+    }
+}
@@ -0,0 +1,104 @@
+//! JPEG marker-segment scanning: locates the `APP1` segment carrying
+//! Exif (`Exif\0\0`) and, separately, the `APP1` segment carrying an
+//! embedded XMP packet (the `http://ns.adobe.com/xap/1.0/\0` signature).
+
+use crate::exif::{check_exif_header, Exif};
+use crate::xmp::XMP_SIGNATURE;
+use crate::{Error, Result};
+
+const APP1: u8 = 0xE1;
+
+/// Walks `data`'s JPEG marker segments, returning the payload of the
+/// first `APP1` segment whose contents satisfy `matches`.
+fn find_app1<'a>(data: &'a [u8], matches: impl Fn(&[u8]) -> bool) -> Option<&'a [u8]> {
+    if data.len() < 2 || data[0..2] != [0xFF, 0xD8] {
+        return None;
+    }
+
+    let mut offset = 2;
+    while offset + 4 <= data.len() {
+        if data[offset] != 0xFF {
+            break;
+        }
+        let marker = data[offset + 1];
+        // SOS (0xDA) starts the entropy-coded scan data; no more markers
+        // of interest follow it.
+        if marker == 0xDA {
+            break;
+        }
+        let len = u16::from_be_bytes([data[offset + 2], data[offset + 3]]) as usize;
+        if len < 2 || offset + 2 + len > data.len() {
+            break;
+        }
+        let payload = &data[offset + 4..offset + 2 + len];
+
+        if marker == APP1 && matches(payload) {
+            return Some(payload);
+        }
+
+        offset += 2 + len;
+    }
+
+    None
+}
+
+/// Locates the raw (header-stripped) TIFF/Exif bytes in a JPEG's `APP1`
+/// Exif segment.
+pub(crate) fn jpeg_extract_exif(data: &[u8]) -> Result<Option<Vec<u8>>> {
+    match find_app1(data, |payload| {
+        check_exif_header(payload).unwrap_or(false)
+    }) {
+        Some(payload) => Ok(Some(payload[6..].to_vec())),
+        None => Ok(None),
+    }
+}
+
+/// Locates the raw RDF/XML XMP packet in a JPEG's `APP1` XMP segment.
+pub(crate) fn jpeg_extract_xmp(data: &[u8]) -> Option<Vec<u8>> {
+    find_app1(data, |payload| payload.starts_with(XMP_SIGNATURE))
+        .map(|payload| payload[XMP_SIGNATURE.len()..].to_vec())
+}
+
+#[deprecated(note = "Please use `MediaParser` instead")]
+pub fn parse_jpeg_exif<R: std::io::Read>(mut reader: R) -> Result<Option<Exif>> {
+    use std::io::Read as _;
+
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+
+    match jpeg_extract_exif(&data)? {
+        Some(tiff) => {
+            let mut prefixed = b"Exif\0\0".to_vec();
+            prefixed.extend_from_slice(&tiff);
+            #[allow(deprecated)]
+            crate::exif::parse_exif(std::io::Cursor::new(prefixed))
+        }
+        None => Err(Error::ExifNotFound),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app1_segment(marker_payload: &[u8]) -> Vec<u8> {
+        let len = (marker_payload.len() + 2) as u16;
+        let mut out = vec![0xFF, APP1];
+        out.extend_from_slice(&len.to_be_bytes());
+        out.extend_from_slice(marker_payload);
+        out
+    }
+
+    #[test]
+    fn extracts_xmp_packet() {
+        let mut xmp_payload = XMP_SIGNATURE.to_vec();
+        xmp_payload.extend_from_slice(b"<x:xmpmeta/>");
+
+        let mut data = vec![0xFF, 0xD8];
+        data.extend(app1_segment(&xmp_payload));
+        data.extend([0xFF, 0xDA, 0x00]); // start of scan (truncated, fine for this test)
+
+        let xmp = jpeg_extract_xmp(&data).unwrap();
+        assert_eq!(xmp, b"<x:xmpmeta/>");
+    }
+}
@@ -8,9 +8,13 @@
 //!   - *.jpg, *.jpeg
 //!   - *.tiff, *.tif
 //!   - *.RAF (Fujifilm RAW)
+//!   - *.CR3 (Canon RAW)
+//!   - *.DNG (Adobe Digital Negative)
 //! - Video/Audio
 //!   - ISO base media file format (ISOBMFF): *.mp4, *.mov, *.3gp, etc.
 //!   - Matroska based file format: *.webm, *.mkv, *.mka, etc.
+//!   - *.mp3 (ID3v2)
+//!   - *.aac (ADTS)
 //!
 //! ## Key Features
 //!
@@ -193,6 +197,48 @@
 //! }
 //! ```
 //!
+//! ## Geotagging from a GPS Track Log
+//!
+//! If a file has no embedded GPS tags, [`GpsTrack`] can derive a
+//! [`GPSInfo`] for it by correlating its capture time against a GPX or
+//! KML track log recorded separately, the way exiftool's `-geotag` option
+//! does.
+//!
+//! ```no_run
+//! use nom_exif::{GpsTrack, GpsTrackOptions};
+//!
+//! fn main() -> nom_exif::Result<()> {
+//!     let gpx = std::fs::read_to_string("./testdata/track.gpx")?;
+//!     let track = GpsTrack::from_gpx(&gpx)?;
+//!
+//!     // `capture_ms` would normally come from the photo's
+//!     // `CreateDate`/`DateTimeOriginal` Exif tags.
+//!     let capture_ms = 1_706_860_800_000;
+//!     let gps_info = track.locate(capture_ms, &GpsTrackOptions::default())?;
+//!     println!("{}", gps_info.format_iso6709());
+//!     Ok(())
+//! }
+//! ```
+//!
+//! ## Embedded XMP
+//!
+//! Some fields, most notably DJI's drone metadata, only ever show up in
+//! an embedded XMP packet, never in Exif. `MediaParser` surfaces it as an
+//! [`Xmp`] the same way it surfaces [`ExifIter`] and [`TrackInfo`]:
+//!
+//! ```no_run
+//! use nom_exif::{MediaParser, MediaSource, Xmp};
+//!
+//! fn main() -> nom_exif::Result<()> {
+//!     let mut parser = MediaParser::new();
+//!     let ms = MediaSource::file_path("./testdata/dji-drone.jpg")?;
+//!
+//!     let xmp: Xmp = parser.parse(ms)?;
+//!     println!("{:?}", xmp.dji_gps_latitude());
+//!     Ok(())
+//! }
+//! ```
+//!
 //! For more usage details, please refer to the [API
 //! documentation](https://docs.rs/nom-exif/latest/nom_exif/).
 //!
@@ -311,7 +357,10 @@ pub use video::{TrackInfo, TrackInfoTag};
 pub use parser_async::{AsyncMediaParser, AsyncMediaSource};
 
 pub use exif::{Exif, ExifIter, ExifTag, GPSInfo, LatLng, ParsedExifEntry};
+pub use gps_track::{GpsTrack, GpsTrackOptions};
+pub use makernote::MakerNoteVendor;
 pub use values::{EntryValue, IRational, URational};
+pub use xmp::Xmp;
 
 #[allow(deprecated)]
 pub use exif::parse_exif;
@@ -323,38 +372,34 @@ pub use exif::parse_exif_async;
 pub use heif::parse_heif_exif;
 #[allow(deprecated)]
 pub use jpeg::parse_jpeg_exif;
-
-// DELETED parse_cr3_exif function
+#[allow(deprecated)]
+pub use cr3::parse_cr3_exif;
 
 pub use error::Error;
 pub type Result<T> = std::result::Result<T, Error>;
 pub(crate) use skip::{Seekable, Unseekable};
 
-use std::io::{Read, Seek};
-// No, Exif is already pub use'd: use crate::exif::Exif;
-// No, Error is already pub use'd: use crate::Error;
-// No, Result is already pub use'd: use crate::Result;
-use crate::bbox::find_box;
-use crate::exif::check_exif_header; // Exif struct is pub use'd, but this function is not
-use crate::loader::BufLoader;
-use crate::mov::extract_moov_body_from_buf;
-
-
 #[allow(deprecated)]
 pub use file::FileFormat;
 
 #[allow(deprecated)]
 pub use mov::{parse_metadata, parse_mov_metadata};
 
+mod aac;
 mod bbox;
 mod buffer;
+mod cr3;
+mod dng;
 mod ebml;
 mod error;
 mod exif;
 mod file;
+mod gps_track;
 mod heif;
+mod id3;
 mod jpeg;
 mod loader;
+mod makernote;
 mod mov;
 mod parser;
 #[cfg(feature = "async")]
@@ -366,117 +411,31 @@ mod slice;
 mod utils;
 mod values;
 mod video;
+mod xmp;
 
 #[cfg(test)]
 mod testkit;
 
-#[allow(unused)]
-#[tracing::instrument(skip_all)]
-pub(crate) fn cr3_extract_exif<R: Read + Seek>(reader: R) -> Result<Option<Vec<u8>>> { // Changed return type
-    let mut loader = BufLoader::<Seekable, _>::new(reader)?;
-    let moov_body_range = loader.load_and_parse(extract_moov_body_from_buf)
-        .map_err(|e| Error::ParseFailed(format!("Failed to extract moov body: {}", e)))?;
-
-    let file_bytes = loader.into_vec();
-    let moov_body = &file_bytes[moov_body_range];
-
-    let mut exif_data_segments = Vec::new();
-
-    for box_type in ["CMT1", "CMT2", "CMT3", "CMT4"].iter() {
-        match find_box(moov_body, box_type) {
-            Ok((_, Some(box_holder))) => {
-                exif_data_segments.push(box_holder.data);
-            }
-            Ok((_, None)) => {
-                tracing::debug!("Box {} not found in moov body", box_type);
-            }
-            Err(e) => {
-                tracing::warn!("Error finding box {}: {:?}", box_type, e);
-            }
-        }
-    }
-
-    if exif_data_segments.is_empty() {
-        tracing::debug!("No CMT boxes with EXIF data found");
-        return Ok(None);
-    }
-
-    let concatenated_cmt_data: Vec<u8> = exif_data_segments.into_iter().flat_map(|d| d.to_vec()).collect();
-
-    if concatenated_cmt_data.is_empty() {
-        tracing::debug!("Concatenated CMT data is empty");
-        return Ok(None);
-    }
-
-    // Minimum length for "Exif\0\0" is 6 bytes. Other TIFF might start directly.
-    if concatenated_cmt_data.len() < 2 { // Smallest TIFF is at least a few bytes for header
-        tracing::debug!("Combined CMT data is too short ({} bytes) to be valid EXIF/TIFF data.", concatenated_cmt_data.len());
-        return Ok(None);
-    }
-
-    // Logic to find actual TIFF data start
-    if concatenated_cmt_data.len() >= 6 && check_exif_header(&concatenated_cmt_data)? {
-        // Starts with "Exif\0\0"
-        return Ok(Some(concatenated_cmt_data[6..].to_vec()));
-    } else if concatenated_cmt_data.len() >= 10 && check_exif_header(&concatenated_cmt_data[4..])? {
-        // Starts with 4-byte prefix then "Exif\0\0"
-        return Ok(Some(concatenated_cmt_data[10..].to_vec()));
-    } else if concatenated_cmt_data.len() >= 8 && (
-        (&concatenated_cmt_data[0..2] == b"II" && concatenated_cmt_data[2..4] == [0x2A, 0x00]) ||
-        (&concatenated_cmt_data[0..2] == b"MM" && concatenated_cmt_data[2..4] == [0x00, 0x2A])
-    ) {
-        // Starts directly with TIFF header (II* or MM*)
-        return Ok(Some(concatenated_cmt_data.to_vec()));
-    } else {
-        tracing::warn!("Could not find valid EXIF/TIFF header in concatenated CMT data.");
-        Ok(None)
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::testkit::*;
-    // ExifTag is no longer used directly in the test for cr3_extract_exif with Vec<u8>
-    // but parse_cr3_exif (the public one) still returns Option<Exif>
-    // and the test cr3_exif_extraction tests parse_cr3_exif.
-    // So ExifTag is still needed for that test.
-    use crate::ExifTag;
-
 
     #[test]
     fn cr3_exif_extraction() {
         let _ = tracing_subscriber::fmt().with_test_writer().try_init();
 
-        // Create MediaSource for the CR3 file
-        let ms = match MediaSource::file_path("testdata/canon-r6.cr3") { // MediaSource is in super
-            Ok(source) => source,
-            Err(e) => panic!("Failed to create MediaSource for 'testdata/canon-r6.cr3': {}", e),
-        };
-
-        assert!(ms.has_exif(), "MediaSource for CR3 should indicate it has EXIF data based on its MIME type.");
-
-        // Use MediaParser to parse
-        let mut parser = MediaParser::new(); // MediaParser is in super
-        let exif_iter_result: Result<ExifIter, Error> = parser.parse(ms); // ExifIter and Error are in super
-
-        match exif_iter_result {
-            Ok(exif_iter) => {
-                let exif: Exif = exif_iter.into(); // Exif is in super
-
-                assert!(!exif.entries.is_empty(), "EXIF data should not be empty for canon-r6.cr3");
+        let ms = MediaSource::file_path("testdata/canon-r6.cr3")
+            .expect("failed to open testdata/canon-r6.cr3");
+        assert!(ms.has_exif());
 
-                let make = exif.get_text(ExifTag::Make); // ExifTag is use crate::ExifTag;
-                let model = exif.get_text(ExifTag::Model);
-                let orientation = exif.get_uint(ExifTag::Orientation);
+        let mut parser = MediaParser::new();
+        let exif_iter: ExifIter = parser.parse(ms).expect("failed to parse CR3 Exif");
+        let exif: Exif = exif_iter.into();
 
-                assert_eq!(make, Some("Canon".to_string()), "Make metadata does not match expected 'Canon'");
-                assert_eq!(model, Some("Canon EOS R6".to_string()), "Model metadata does not match expected 'Canon EOS R6'");
-                assert_eq!(orientation, Some(1), "Orientation metadata does not match expected '1' (Horizontal (normal))");
-            }
-            Err(e) => {
-                panic!("Error parsing CR3 EXIF data via MediaParser: {:?}", e);
-            }
-        }
+        assert!(!exif.entries.is_empty());
+        assert_eq!(exif.get_text(ExifTag::Make), Some("Canon".to_string()));
+        assert_eq!(exif.get_text(ExifTag::Model), Some("Canon EOS R6".to_string()));
+        assert_eq!(exif.get_uint(ExifTag::Orientation), Some(1));
     }
 }
@@ -0,0 +1,43 @@
+//! Buffered loading of an entire reader's contents, so box/IFD scanners
+//! can work against a plain `&[u8]` regardless of where the bytes came
+//! from (a file, a `TcpStream`, an in-memory buffer, ...).
+
+use std::io::Read;
+use std::marker::PhantomData;
+use std::ops::Range;
+
+use crate::Result;
+
+/// Reads an entire `R` into memory, then hands a parser function a
+/// `&[u8]` view of it to locate a sub-range of interest (e.g. `moov`'s
+/// body). The `Marker` type parameter ([`crate::skip::Seekable`] /
+/// [`crate::skip::Unseekable`]) only distinguishes call sites today;
+/// both load the same way.
+pub(crate) struct BufLoader<Marker, R> {
+    data: Vec<u8>,
+    _reader: PhantomData<R>,
+    _marker: PhantomData<Marker>,
+}
+
+impl<Marker, R: Read> BufLoader<Marker, R> {
+    pub(crate) fn new(mut reader: R) -> Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        Ok(Self {
+            data,
+            _reader: PhantomData,
+            _marker: PhantomData,
+        })
+    }
+
+    pub(crate) fn load_and_parse<F>(&mut self, f: F) -> Result<Range<usize>>
+    where
+        F: FnOnce(&[u8]) -> Result<Range<usize>>,
+    {
+        f(&self.data)
+    }
+
+    pub(crate) fn into_vec(self) -> Vec<u8> {
+        self.data
+    }
+}
@@ -0,0 +1,206 @@
+//! Vendor-specific maker-note decoding for Canon, Nikon and Fujifilm.
+//!
+//! Without this module, the `MakerNote` Exif tag surfaces only as an
+//! opaque `Undefined[..]` blob (see `rexiftool`'s `MakerNote => Undefined[0x30]`
+//! output). The EXIF IFD parser dispatches here once it has already parsed
+//! `Make`/`Model`: [`decode`] picks the vendor-specific sub-IFD parser for
+//! the maker-note bytes, which handles each vendor's header quirks and
+//! returns the contained entries as a normal, already-parsed IFD, namespaced
+//! so they don't collide with standard Exif tags.
+
+use crate::exif::{read_u32, ParsedExifEntry};
+use crate::{Error, Result};
+
+/// The vendor dialect a maker note was decoded with, so callers can tell
+/// which tag table a [`ParsedExifEntry`] came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MakerNoteVendor {
+    Canon,
+    Nikon,
+    Fujifilm,
+}
+
+/// Picks a vendor-specific decoder based on the already-parsed `Make`
+/// tag and decodes the `MakerNote` entry at `mn_offset` (counted from the
+/// start of `tiff`, the enclosing TIFF buffer the `MakerNote` entry was
+/// found in) into a flat list of entries.
+///
+/// Returns `Ok(None)` for vendors without a decoder (the caller should
+/// keep surfacing the tag as an opaque `Undefined` blob in that case).
+pub(crate) fn decode(
+    make: &str,
+    tiff: &[u8],
+    mn_offset: usize,
+    tiff_is_le: bool,
+) -> Result<Option<(MakerNoteVendor, Vec<ParsedExifEntry>)>> {
+    let make = make.trim();
+
+    if make.eq_ignore_ascii_case("Canon") {
+        return Ok(Some((
+            MakerNoteVendor::Canon,
+            decode_canon(tiff, mn_offset, tiff_is_le)?,
+        )));
+    }
+
+    let data = tiff
+        .get(mn_offset..)
+        .ok_or_else(|| Error::ParseFailed("MakerNote offset is out of range".into()))?;
+
+    if make.to_ascii_uppercase().starts_with("NIKON") {
+        return Ok(Some((MakerNoteVendor::Nikon, decode_nikon(data)?)));
+    }
+
+    if make.eq_ignore_ascii_case("FUJIFILM") {
+        return Ok(Some((MakerNoteVendor::Fujifilm, decode_fujifilm(data)?)));
+    }
+
+    Ok(None)
+}
+
+/// Canon maker notes have no header at all: the `MakerNote` value is a
+/// headerless IFD, sharing the enclosing TIFF's byte order and using
+/// offsets relative to the *start of the TIFF*, not the start of the
+/// maker note. Unlike Nikon/Fujifilm, this decoder walks `tiff` itself
+/// (starting at `mn_offset`) rather than an extracted slice, so any
+/// offset-valued entries resolve correctly against the TIFF base.
+fn decode_canon(tiff: &[u8], mn_offset: usize, tiff_is_le: bool) -> Result<Vec<ParsedExifEntry>> {
+    decode_ifd_entries(tiff, mn_offset, tiff_is_le)
+}
+
+/// Nikon (type 3, used by all modern bodies) maker notes start with the
+/// 6-byte signature `Nikon\0`, a 2-byte format version, 2 further bytes,
+/// then an embedded TIFF header (`II*\0`/`MM\0*` + offset) that establishes its own byte
+/// order and its own offset base (counted from the first byte of the
+/// embedded TIFF header, i.e. offset 10 into the maker note).
+fn decode_nikon(data: &[u8]) -> Result<Vec<ParsedExifEntry>> {
+    const SIG: &[u8] = b"Nikon\0";
+    if !data.starts_with(SIG) {
+        return Err(Error::ParseFailed(
+            "Nikon maker note is missing the 'Nikon\\0' signature".into(),
+        ));
+    }
+
+    let tiff_header_start = SIG.len() + 4; // skip signature + format version + 2 unknown bytes
+    let tiff = data
+        .get(tiff_header_start..)
+        .ok_or_else(|| Error::ParseFailed("Nikon maker note is truncated".into()))?;
+
+    let is_le = match tiff.get(0..2) {
+        Some(b"II") => true,
+        Some(b"MM") => false,
+        _ => {
+            return Err(Error::ParseFailed(
+                "Nikon maker note has an invalid embedded TIFF header".into(),
+            ))
+        }
+    };
+    let ifd0_offset = read_u32(tiff, 4, is_le)?;
+
+    decode_ifd_entries(tiff, ifd0_offset as usize, is_le)
+}
+
+/// Fujifilm maker notes start with the 8-byte signature `FUJIFILM`
+/// followed by a 4-byte little-endian offset to the IFD, both counted
+/// from the start of the maker note; the IFD's own offset-valued entries
+/// are likewise relative to the start of the maker note (not a nested
+/// TIFF header as with Nikon).
+fn decode_fujifilm(data: &[u8]) -> Result<Vec<ParsedExifEntry>> {
+    const SIG: &[u8] = b"FUJIFILM";
+    if !data.starts_with(SIG) {
+        return Err(Error::ParseFailed(
+            "Fujifilm maker note is missing the 'FUJIFILM' signature".into(),
+        ));
+    }
+
+    let ifd_offset = data
+        .get(SIG.len()..SIG.len() + 4)
+        .ok_or_else(|| Error::ParseFailed("Fujifilm maker note is truncated".into()))
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize)?;
+
+    // Fujifilm IFD entries always use little-endian, regardless of the
+    // enclosing TIFF's byte order, and are offset-addressed relative to
+    // the start of the maker note itself, so `data` (not a slice starting
+    // at `ifd_offset`) is the base `decode_ifd_entries` resolves against.
+    decode_ifd_entries(data, ifd_offset, true)
+}
+
+/// Walks a standard TIFF-style IFD (2-byte entry count, 12-byte entries)
+/// at `offset` within `buf`, resolving any offset-valued entries against
+/// `buf` itself, and returns the entries with their raw (non-namespaced)
+/// tags — the caller re-tags them via [`crate::ExifTag::MakerNoteTag`]
+/// once the vendor is known.
+fn decode_ifd_entries(buf: &[u8], offset: usize, is_le: bool) -> Result<Vec<ParsedExifEntry>> {
+    crate::exif::parse_ifd_entries(buf, offset, is_le)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_mislabeled_nikon_note() {
+        let data = b"NotNikon stuff that isn't a real maker note at all";
+        assert!(decode_nikon(data).is_err());
+    }
+
+    #[test]
+    fn rejects_mislabeled_fujifilm_note() {
+        let data = b"not fuji";
+        assert!(decode_fujifilm(data).is_err());
+    }
+
+    #[test]
+    fn dispatches_on_make() {
+        // No decoder for this vendor: falls back to opaque Undefined.
+        assert!(decode("PENTAX", &[], 0, true).unwrap().is_none());
+    }
+
+    #[test]
+    fn decodes_nikon_maker_note_happy_path() {
+        let mut data = Vec::new();
+        data.extend_from_slice(b"Nikon\0");
+        data.extend_from_slice(&[2, 0]); // format version
+        data.extend_from_slice(&[0, 0]); // unknown/padding
+
+        let tiff_header_start = data.len();
+        data.extend_from_slice(b"II");
+        data.extend_from_slice(&0x2Au16.to_le_bytes());
+        data.extend_from_slice(&8u32.to_le_bytes()); // ifd0 offset, relative to tiff_header_start
+
+        assert_eq!(data.len() - tiff_header_start, 8);
+        data.extend_from_slice(&1u16.to_le_bytes()); // entry count
+        data.extend_from_slice(&0x0001u16.to_le_bytes()); // tag id
+        data.extend_from_slice(&2u16.to_le_bytes()); // type: ASCII
+        data.extend_from_slice(&4u32.to_le_bytes()); // count
+        data.extend_from_slice(b"RAW\0"); // inline value
+        data.extend_from_slice(&0u32.to_le_bytes()); // next IFD offset
+
+        let entries = decode_nikon(&data).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].tag, crate::ExifTag::Unknown(0x0001));
+        assert_eq!(
+            entries[0].value,
+            crate::EntryValue::Text("RAW".to_string())
+        );
+    }
+
+    #[test]
+    fn decodes_fujifilm_maker_note_happy_path() {
+        let mut data = Vec::new();
+        data.extend_from_slice(b"FUJIFILM");
+        data.extend_from_slice(&12u32.to_le_bytes()); // IFD offset within the note
+        data.extend_from_slice(&1u16.to_le_bytes()); // entry count
+        data.extend_from_slice(&0x1000u16.to_le_bytes()); // tag id
+        data.extend_from_slice(&2u16.to_le_bytes()); // type: ASCII
+        data.extend_from_slice(&4u32.to_le_bytes()); // count
+        data.extend_from_slice(b"RAW\0"); // inline value
+
+        let entries = decode_fujifilm(&data).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].tag, crate::ExifTag::Unknown(0x1000));
+        assert_eq!(
+            entries[0].value,
+            crate::EntryValue::Text("RAW".to_string())
+        );
+    }
+}
@@ -0,0 +1,68 @@
+//! ISOBMFF (`.mov`/`.mp4`/`.3gp`/HEIF/CR3, ...) box-level helpers:
+//! locating `moov`'s body (used by CR3's `CMT` extraction and by track
+//! metadata extraction) and deriving a [`TrackInfo`] from the standard
+//! `mvhd` box.
+
+use std::ops::Range;
+
+use crate::bbox::find_box;
+use crate::video::TrackInfo;
+use crate::{Error, Result};
+
+/// Returns the byte range of `moov`'s body within `data`.
+pub(crate) fn extract_moov_body_from_buf(data: &[u8]) -> Result<Range<usize>> {
+    let (_, found) = find_box(data, "moov")?;
+    let found = found.ok_or_else(|| Error::ParseFailed("no 'moov' box found".into()))?;
+
+    // `find_box` hands back a slice; recover its offset within `data` by
+    // pointer arithmetic so we can return an owned `Range` rather than a
+    // borrow tied to `data`'s lifetime.
+    let start = found.data.as_ptr() as usize - data.as_ptr() as usize;
+    Ok(start..start + found.data.len())
+}
+
+/// Extracts basic track metadata (currently just duration, derived from
+/// `mvhd`'s timescale/duration fields) from an ISOBMFF file's `moov` box.
+pub(crate) fn extract_track_info(data: &[u8]) -> Result<TrackInfo> {
+    let mut info = TrackInfo::default();
+
+    let moov_range = extract_moov_body_from_buf(data)?;
+    let moov = &data[moov_range];
+
+    if let (_, Some(mvhd)) = find_box(moov, "mvhd")? {
+        if mvhd.data.len() >= 20 {
+            let version = mvhd.data[0];
+            let (timescale, duration) = if version == 1 && mvhd.data.len() >= 32 {
+                (
+                    u32::from_be_bytes(mvhd.data[20..24].try_into().unwrap()),
+                    u64::from_be_bytes(mvhd.data[24..32].try_into().unwrap()),
+                )
+            } else {
+                (
+                    u32::from_be_bytes(mvhd.data[12..16].try_into().unwrap()),
+                    u32::from_be_bytes(mvhd.data[16..20].try_into().unwrap()) as u64,
+                )
+            };
+            if timescale > 0 {
+                let duration_ms = duration * 1000 / timescale as u64;
+                info.put(crate::video::TrackInfoTag::DurationMs, duration_ms.to_string());
+            }
+        }
+    }
+
+    Ok(info)
+}
+
+#[deprecated(note = "Please use `MediaParser` instead")]
+pub fn parse_metadata<R: std::io::Read>(mut reader: R) -> Result<TrackInfo> {
+    use std::io::Read as _;
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+    extract_track_info(&data)
+}
+
+#[deprecated(note = "Please use `MediaParser` instead")]
+pub fn parse_mov_metadata<R: std::io::Read>(reader: R) -> Result<TrackInfo> {
+    #[allow(deprecated)]
+    parse_metadata(reader)
+}
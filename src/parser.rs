@@ -0,0 +1,158 @@
+//! The unified sync workflow: [`MediaSource`] wraps any `Read` (or
+//! `Read + Seek`) source and sniffs its [`FileFormat`]; [`MediaParser`]
+//! then produces whichever output type the caller asks for
+//! ([`ExifIter`], [`TrackInfo`] or [`Xmp`]) by dispatching on that
+//! format.
+
+use std::fs::File;
+use std::io::Read;
+use std::net::TcpStream;
+use std::path::Path;
+
+use crate::aac::parse_adts;
+use crate::cr3::cr3_extract_exif;
+use crate::exif::{check_exif_header, ExifIter};
+use crate::file::FileFormat;
+use crate::id3::parse_id3;
+use crate::mov::extract_track_info;
+use crate::video::TrackInfo;
+use crate::xmp::Xmp;
+use crate::{Error, Result};
+
+/// A multimedia file, loaded into memory and sniffed for its
+/// [`FileFormat`]. Build one from a file path, a `TcpStream`, or any
+/// `Read`/`Read + Seek` source, then hand it to [`MediaParser::parse`].
+pub struct MediaSource {
+    data: Vec<u8>,
+    format: FileFormat,
+}
+
+impl MediaSource {
+    fn from_reader<R: Read>(mut reader: R) -> Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        let format = FileFormat::detect(&data)?;
+        Ok(Self { data, format })
+    }
+
+    /// Opens and loads the file at `path`.
+    pub fn file_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::from_reader(File::open(path)?)
+    }
+
+    /// Reads an entire `TcpStream` into memory before parsing.
+    pub fn tcp_stream(stream: TcpStream) -> Result<Self> {
+        Self::from_reader(stream)
+    }
+
+    /// Builds a `MediaSource` from any `Read + Seek` source.
+    pub fn seekable<R: Read>(reader: R) -> Result<Self> {
+        Self::from_reader(reader)
+    }
+
+    /// Builds a `MediaSource` from any `Read` source.
+    pub fn unseekable<R: Read>(reader: R) -> Result<Self> {
+        Self::from_reader(reader)
+    }
+
+    /// `true` if this source's format carries Exif metadata.
+    pub fn has_exif(&self) -> bool {
+        self.format.has_exif()
+    }
+
+    /// `true` if this source's format carries track (video/audio)
+    /// metadata.
+    pub fn has_track(&self) -> bool {
+        self.format.has_track()
+    }
+
+    /// `true` if this source's format may carry an embedded XMP packet.
+    pub fn has_xmp(&self) -> bool {
+        matches!(
+            self.format,
+            FileFormat::Jpeg | FileFormat::Heif | FileFormat::Raf | FileFormat::Cr3
+        )
+    }
+}
+
+/// Parses a [`MediaSource`] into `Self`, dispatching on its
+/// [`FileFormat`]. Implemented for every output type [`MediaParser::parse`]
+/// supports.
+pub trait FromMediaSource: Sized {
+    fn from_media_source(ms: MediaSource) -> Result<Self>;
+}
+
+impl FromMediaSource for ExifIter {
+    fn from_media_source(ms: MediaSource) -> Result<Self> {
+        let tiff = match ms.format {
+            FileFormat::Jpeg => crate::jpeg::jpeg_extract_exif(&ms.data)?,
+            FileFormat::Heif => crate::heif::heif_extract_exif(&ms.data)?,
+            FileFormat::Raf => crate::raf::raf_extract_exif(&ms.data)?,
+            FileFormat::Cr3 => cr3_extract_exif(std::io::Cursor::new(ms.data.as_slice()))?,
+            FileFormat::Tiff | FileFormat::Dng => Some(ms.data[..].to_vec()),
+            _ => return Err(Error::ExifNotFound),
+        };
+        let tiff = tiff.ok_or(Error::ExifNotFound)?;
+        parse_tiff_entries(&tiff)
+    }
+}
+
+impl FromMediaSource for TrackInfo {
+    fn from_media_source(ms: MediaSource) -> Result<Self> {
+        match ms.format {
+            FileFormat::Mov | FileFormat::Mp4 => extract_track_info(&ms.data),
+            FileFormat::Mp3 => parse_id3(&ms.data),
+            FileFormat::Aac => parse_adts(&ms.data),
+            FileFormat::WebM | FileFormat::Matroska => Ok(TrackInfo::default()),
+            _ => Err(Error::ParseFailed("source has no track".into())),
+        }
+    }
+}
+
+impl FromMediaSource for Xmp {
+    fn from_media_source(ms: MediaSource) -> Result<Self> {
+        let packet = match ms.format {
+            FileFormat::Jpeg => crate::jpeg::jpeg_extract_xmp(&ms.data),
+            FileFormat::Heif => crate::heif::heif_extract_xmp(&ms.data)?,
+            FileFormat::Raf => crate::raf::raf_extract_xmp(&ms.data),
+            FileFormat::Cr3 => crate::bbox::find_xmp_packet(&ms.data)?.map(|p| p.to_vec()),
+            _ => None,
+        };
+        match packet {
+            Some(bytes) => Xmp::from_packet(&bytes),
+            None => Ok(Xmp::default()),
+        }
+    }
+}
+
+/// Walks `tiff`'s IFD0 (and its `ExifOffset`/`MakerNote`/`SubIFDs`
+/// entries) into an [`ExifIter`].
+fn parse_tiff_entries(tiff: &[u8]) -> Result<ExifIter> {
+    let (entries, gps_info) = if check_exif_header(tiff)? {
+        crate::exif::parse_tiff_ifd(&tiff[6..])?
+    } else {
+        crate::exif::parse_tiff_ifd(tiff)?
+    };
+    Ok(ExifIter { entries, gps_info })
+}
+
+/// Parses [`MediaSource`]s into the requested output type. It keeps no
+/// buffer between [`Self::parse`] calls: each [`MediaSource`] loads and
+/// owns its own bytes.
+#[derive(Debug, Default)]
+pub struct MediaParser {
+    _reserved_capacity: usize,
+}
+
+impl MediaParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses `ms` into whichever output type `T` the caller asked for
+    /// (inferred from the binding's type, e.g. `let exif: ExifIter =
+    /// parser.parse(ms)?;`).
+    pub fn parse<T: FromMediaSource>(&mut self, ms: MediaSource) -> Result<T> {
+        T::from_media_source(ms)
+    }
+}
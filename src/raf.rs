@@ -0,0 +1,36 @@
+//! Fujifilm RAF: Exif and XMP both live in RAF's embedded JPEG preview,
+//! so extraction delegates to the same `APP1` scanning [`crate::jpeg`]
+//! uses for standalone JPEGs.
+
+use crate::jpeg::{jpeg_extract_exif, jpeg_extract_xmp};
+use crate::Result;
+
+const RAF_MAGIC: &[u8] = b"FUJIFILMCCD-RAW";
+
+/// Locates the embedded JPEG preview's start-of-image marker and returns
+/// a slice starting there, or `None` if `data` isn't a recognizable RAF
+/// file.
+fn embedded_jpeg(data: &[u8]) -> Option<&[u8]> {
+    if !data.starts_with(RAF_MAGIC) {
+        return None;
+    }
+    let start = data
+        .windows(2)
+        .position(|w| w == [0xFF, 0xD8])?;
+    Some(&data[start..])
+}
+
+/// Locates the raw (header-stripped) TIFF/Exif bytes in a RAF file's
+/// embedded JPEG preview.
+pub(crate) fn raf_extract_exif(data: &[u8]) -> Result<Option<Vec<u8>>> {
+    match embedded_jpeg(data) {
+        Some(jpeg) => jpeg_extract_exif(jpeg),
+        None => Ok(None),
+    }
+}
+
+/// Locates the raw RDF/XML XMP packet in a RAF file's embedded JPEG
+/// preview.
+pub(crate) fn raf_extract_xmp(data: &[u8]) -> Option<Vec<u8>> {
+    embedded_jpeg(data).and_then(jpeg_extract_xmp)
+}
@@ -0,0 +1,13 @@
+//! Marker types distinguishing sources that support `Seek` from those
+//! that don't, as the type parameter of [`crate::loader::BufLoader`]
+//! (which currently loads both kinds the same way).
+
+/// Marks a [`crate::loader::BufLoader`] whose underlying reader
+/// implements `Seek`.
+#[derive(Debug, Default)]
+pub(crate) struct Seekable;
+
+/// Marks a [`crate::loader::BufLoader`] whose underlying reader is
+/// `Read`-only.
+#[derive(Debug, Default)]
+pub(crate) struct Unseekable;
@@ -0,0 +1,86 @@
+//! Scalar Exif/track-info value types.
+
+use std::fmt;
+
+/// An unsigned rational number, stored as `numerator/denominator`, as
+/// used by tags like `FNumber` or GPS coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct URational(pub u32, pub u32);
+
+impl fmt::Display for URational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.0, self.1)
+    }
+}
+
+/// A signed rational number, as used by tags like `ExposureBiasValue`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IRational(pub i32, pub i32);
+
+impl fmt::Display for IRational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.0, self.1)
+    }
+}
+
+/// A parsed Exif/track-info entry value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EntryValue {
+    Text(String),
+    U32(u32),
+    I32(i32),
+    URational(URational),
+    IRational(IRational),
+    Undefined(Vec<u8>),
+}
+
+impl EntryValue {
+    /// Returns the value as a string slice, if it's text.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            EntryValue::Text(s) => Some(s.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as an unsigned integer, if it was stored as a
+    /// `U32` or a non-negative `I32`; rationals give `None`.
+    pub fn as_uint(&self) -> Option<u64> {
+        match self {
+            EntryValue::U32(v) => Some(*v as u64),
+            EntryValue::I32(v) if *v >= 0 => Some(*v as u64),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for EntryValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntryValue::Text(s) => write!(f, "{s}"),
+            EntryValue::U32(v) => write!(f, "{v}"),
+            EntryValue::I32(v) => write!(f, "{v}"),
+            EntryValue::URational(r) => write!(f, "{r}"),
+            EntryValue::IRational(r) => write!(f, "{r}"),
+            EntryValue::Undefined(b) => write!(f, "Undefined[{:#x}]", b.len()),
+        }
+    }
+}
+
+impl From<&str> for EntryValue {
+    fn from(s: &str) -> Self {
+        EntryValue::Text(s.to_string())
+    }
+}
+
+impl From<String> for EntryValue {
+    fn from(s: String) -> Self {
+        EntryValue::Text(s)
+    }
+}
+
+impl From<u32> for EntryValue {
+    fn from(v: u32) -> Self {
+        EntryValue::U32(v)
+    }
+}
@@ -0,0 +1,59 @@
+//! Track metadata shared by every non-Exif container this crate supports
+//! (ISOBMFF video/audio, Matroska, MP3, AAC): a simple tag -> value map,
+//! mirroring [`crate::Exif`]'s *get*-style API.
+
+use std::collections::HashMap;
+
+use crate::exif::GPSInfo;
+use crate::values::EntryValue;
+use crate::Result;
+
+/// A tag in [`TrackInfo`]'s namespace. Unlike [`crate::ExifTag`] this
+/// isn't tied to the Exif/TIFF tag space — it's this crate's own
+/// vocabulary for the handful of fields that matter across container
+/// formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrackInfoTag {
+    Make,
+    Model,
+    Software,
+    CreateDate,
+    DurationMs,
+    SampleRate,
+    ImageWidth,
+    ImageHeight,
+    GpsIso6709,
+    Title,
+    Artist,
+    Album,
+    /// Raw image bytes from an embedded cover-art frame (ID3v2 `APIC`),
+    /// which for JPEG/TIFF art may itself carry an Exif block — callers
+    /// wanting that metadata can feed the bytes back through
+    /// [`crate::MediaSource::seekable`].
+    CoverArt,
+}
+
+/// Get-style access to a track's metadata, produced by
+/// [`crate::MediaParser::parse`] for any `MediaSource` where
+/// `has_track()` is `true`.
+#[derive(Debug, Clone, Default)]
+pub struct TrackInfo {
+    entries: HashMap<TrackInfoTag, EntryValue>,
+}
+
+impl TrackInfo {
+    pub(crate) fn put(&mut self, tag: TrackInfoTag, value: impl Into<EntryValue>) {
+        self.entries.insert(tag, value.into());
+    }
+
+    pub fn get(&self, tag: TrackInfoTag) -> Option<&EntryValue> {
+        self.entries.get(&tag)
+    }
+
+    /// Always returns `Ok(None)`: the `GpsIso6709` tag
+    /// (`"+27.1281+100.2508+000.000/"`) is not parsed into a [`GPSInfo`];
+    /// read it as text with [`Self::get`].
+    pub fn get_gps_info(&self) -> Result<Option<GPSInfo>> {
+        Ok(None)
+    }
+}
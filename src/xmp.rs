@@ -0,0 +1,214 @@
+//! Embedded XMP packet parsing.
+//!
+//! Many modern phones and drones store their most useful positioning and
+//! orientation data only in XMP, not Exif. This module parses the RDF/XML
+//! packet embedded in JPEG (`APP1` segments carrying the
+//! `http://ns.adobe.com/xap/1.0/\0` namespace signature), HEIF/ISOBMFF
+//! (`uuid`/`mime` items and `XMP_` boxes) and RAF files into a flat map of
+//! fully-qualified property names to string values, exposed via
+//! [`Xmp::get`] and a handful of convenience getters for fields that have
+//! no Exif equivalent (e.g. DJI's drone metadata).
+
+use std::collections::HashMap;
+
+use crate::{Error, Result};
+
+/// The signature that marks a JPEG `APP1` segment as carrying an XMP
+/// packet, as opposed to Exif (`Exif\0\0`).
+pub(crate) const XMP_SIGNATURE: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+
+/// Parsed XMP metadata: a flat map from fully-qualified property name
+/// (`namespace:local_name`) to string value.
+///
+/// Obtained via [`MediaParser::parse`](crate::MediaParser::parse) for any
+/// `MediaSource` that carries an embedded XMP packet.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Xmp {
+    entries: HashMap<String, String>,
+}
+
+impl Xmp {
+    /// Parses a raw RDF/XML XMP packet (the bytes following the
+    /// [`XMP_SIGNATURE`] in a JPEG `APP1` segment, or the payload of an
+    /// ISOBMFF `XMP_`/`uuid` box) into an [`Xmp`].
+    pub fn from_packet(xml: &[u8]) -> Result<Self> {
+        let xml = std::str::from_utf8(xml)
+            .map_err(|e| Error::ParseFailed(format!("XMP packet is not valid utf-8: {e}")))?;
+
+        let mut entries = HashMap::new();
+        parse_rdf_description_attrs(xml, &mut entries);
+        parse_rdf_property_elements(xml, &mut entries);
+
+        Ok(Self { entries })
+    }
+
+    /// Looks up a property by namespace prefix and local name, e.g.
+    /// `get("drone-dji", "GpsLatitude")`.
+    pub fn get(&self, namespace: &str, local_name: &str) -> Option<&str> {
+        self.entries
+            .get(&format!("{namespace}:{local_name}"))
+            .map(|s| s.as_str())
+    }
+
+    /// Returns `true` if no XMP properties were found.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over every `(namespace:local_name, value)` pair.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// `dc:description`, the standard Dublin Core description field.
+    pub fn description(&self) -> Option<&str> {
+        self.get("dc", "description")
+    }
+
+    /// `xmp:CreateDate`.
+    pub fn create_date(&self) -> Option<&str> {
+        self.get("xmp", "CreateDate")
+    }
+
+    /// `photoshop:DateCreated`.
+    pub fn date_created(&self) -> Option<&str> {
+        self.get("photoshop", "DateCreated")
+    }
+
+    /// DJI's `drone-dji:GpsLatitude`, as found in most DJI drone JPEGs.
+    pub fn dji_gps_latitude(&self) -> Option<f64> {
+        self.get("drone-dji", "GpsLatitude")?.parse().ok()
+    }
+
+    /// DJI's `drone-dji:GpsLongitude`.
+    pub fn dji_gps_longitude(&self) -> Option<f64> {
+        self.get("drone-dji", "GpsLongitude")?.parse().ok()
+    }
+
+    /// DJI's `drone-dji:RelativeAltitude`, the altitude above the
+    /// drone's takeoff point, in meters.
+    pub fn dji_relative_altitude(&self) -> Option<f64> {
+        self.get("drone-dji", "RelativeAltitude")?.parse().ok()
+    }
+
+    /// DJI's `drone-dji:GimbalYawDegree`.
+    pub fn dji_gimbal_yaw_degree(&self) -> Option<f64> {
+        self.get("drone-dji", "GimbalYawDegree")?.parse().ok()
+    }
+}
+
+/// Parses `<rdf:Description ns:Attr="value" ...>` attribute-form
+/// properties, the form DJI and most camera apps emit.
+fn parse_rdf_description_attrs(xml: &str, out: &mut HashMap<String, String>) {
+    let mut rest = xml;
+    while let Some(start) = rest.find("<rdf:Description") {
+        let after = &rest[start + "<rdf:Description".len()..];
+        let Some(tag_end) = after.find('>') else {
+            break;
+        };
+        let attrs = &after[..tag_end];
+        for (name, value) in scan_attrs(attrs) {
+            if name.contains(':') && name != "rdf:about" && !name.starts_with("xmlns:") {
+                out.insert(name.to_string(), value.to_string());
+            }
+        }
+        rest = &after[tag_end + 1..];
+    }
+}
+
+/// Parses `<ns:LocalName>value</ns:LocalName>` element-form properties.
+fn parse_rdf_property_elements(xml: &str, out: &mut HashMap<String, String>) {
+    let mut rest = xml;
+    while let Some(lt) = rest.find('<') {
+        let after = &rest[lt + 1..];
+        if after.starts_with('/') || after.starts_with('?') || after.starts_with('!') {
+            rest = &after[1..];
+            continue;
+        }
+        let Some(tag_end) = after.find(|c: char| c == '>' || c.is_whitespace()) else {
+            break;
+        };
+        let name = &after[..tag_end];
+        if !name.contains(':') || name.starts_with("rdf:") || name.starts_with("x:") {
+            rest = &rest[lt + 1..];
+            continue;
+        }
+
+        let Some(gt) = after.find('>') else { break };
+        if after[..gt].ends_with('/') {
+            // Self-closing, no text content.
+            rest = &after[gt + 1..];
+            continue;
+        }
+
+        let close = format!("</{name}>");
+        if let Some(close_at) = after[gt + 1..].find(close.as_str()) {
+            let text = after[gt + 1..gt + 1 + close_at].trim();
+            if !text.is_empty() && !text.starts_with('<') {
+                out.entry(name.to_string()).or_insert_with(|| text.to_string());
+            }
+            rest = &after[gt + 1 + close_at + close.len()..];
+        } else {
+            rest = &after[gt + 1..];
+        }
+    }
+}
+
+/// Scans `name="value"` pairs out of a tag's attribute list.
+fn scan_attrs(attrs: &str) -> Vec<(&str, &str)> {
+    let mut out = Vec::new();
+    let mut rest = attrs;
+    while let Some(eq) = rest.find("=\"") {
+        let name = rest[..eq].trim();
+        let name = name.rsplit(|c: char| c.is_whitespace()).next().unwrap_or(name);
+        let value_start = eq + 2;
+        let Some(value_end) = rest[value_start..].find('"') else {
+            break;
+        };
+        if !name.is_empty() {
+            out.push((name, &rest[value_start..value_start + value_end]));
+        }
+        rest = &rest[value_start + value_end + 1..];
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_dji_attribute_form() {
+        let packet = br#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+            <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+                <rdf:Description rdf:about=""
+                    xmlns:drone-dji="http://www.dji.com/drone-dji/1.0/"
+                    drone-dji:GpsLatitude="22.123456"
+                    drone-dji:GpsLongitude="113.123456"
+                    drone-dji:RelativeAltitude="+50.30"
+                    drone-dji:GimbalYawDegree="+90.00">
+                </rdf:Description>
+            </rdf:RDF>
+        </x:xmpmeta>"#;
+
+        let xmp = Xmp::from_packet(packet).unwrap();
+        assert_eq!(xmp.dji_gps_latitude(), Some(22.123456));
+        assert_eq!(xmp.dji_gps_longitude(), Some(113.123456));
+        assert_eq!(xmp.dji_relative_altitude(), Some(50.30));
+        assert_eq!(xmp.dji_gimbal_yaw_degree(), Some(90.00));
+    }
+
+    #[test]
+    fn parses_element_form() {
+        let packet = br#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+            <rdf:Description rdf:about="">
+                <dc:description>A sample photo</dc:description>
+                <xmp:CreateDate>2024-02-02T08:09:57Z</xmp:CreateDate>
+            </rdf:Description>
+        </rdf:RDF>"#;
+
+        let xmp = Xmp::from_packet(packet).unwrap();
+        assert_eq!(xmp.description(), Some("A sample photo"));
+        assert_eq!(xmp.create_date(), Some("2024-02-02T08:09:57Z"));
+    }
+}